@@ -0,0 +1,306 @@
+use std::{error::Error, ops::RangeInclusive, path::PathBuf, str::FromStr, time::Duration};
+
+use clap::{error::ErrorKind, CommandFactory, Parser};
+use common::{
+    answers::Tally,
+    bench::{Baseline, Timings},
+    cli::{bench_day, run_day},
+    fetch::Fetcher,
+    input::{day_dir, InputResolver, InputSource},
+    report::Format,
+    Part, Solution,
+};
+
+#[derive(Parser)]
+#[command(about = "Run the Advent of Code 2022 solutions")]
+struct Args {
+    /// The days to run: a single day (`7`), an inclusive range (`3..9`), or `all`
+    days: Days,
+
+    /// Only run one part of each day
+    #[arg(long)]
+    part: Option<Part>,
+
+    /// Path to the puzzle input, or `-` for stdin. Only makes sense when running a single day
+    #[arg(long)]
+    input: Option<String>,
+
+    /// Save the computed answers as the known-good ones for each day's input
+    #[arg(long)]
+    record: bool,
+
+    /// Print the answers as text, or as a JSON or CSV record per day and part
+    #[arg(long, default_value_t = Format::Text, conflicts_with = "bench")]
+    format: Format,
+
+    /// Download each day's input and puzzle text instead of solving anything, skipping any that
+    /// are already there. Needs `AOC_SESSION` set to the session cookie
+    #[arg(long, conflicts_with_all = ["input", "record", "bench", "format"])]
+    fetch: bool,
+
+    /// Time parsing and each part instead of checking the answers
+    #[arg(long, conflicts_with = "record")]
+    bench: bool,
+
+    /// How many times to run each phase when benchmarking
+    #[arg(long, default_value_t = 10, requires = "bench")]
+    runs: usize,
+
+    /// The baseline file that benchmarks are compared against
+    #[arg(long, default_value = "bench-baseline.txt", requires = "bench")]
+    baseline: PathBuf,
+
+    /// Save this run's medians into the baseline file
+    #[arg(long, requires = "bench")]
+    save_baseline: bool,
+
+    /// How much slower than the baseline (in percent) counts as a regression
+    #[arg(long, default_value_t = 10.0, requires = "bench")]
+    threshold: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Days(RangeInclusive<u8>);
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let day = |d: &str| -> Result<u8, String> {
+            match d.trim().parse() {
+                Ok(n @ 1..=25) => Ok(n),
+                _ => Err(format!("{d} is not a day between 1 and 25")),
+            }
+        };
+
+        let range = if s == "all" {
+            1..=25
+        } else if let Some((start, end)) = s.split_once("..=").or_else(|| s.split_once("..")) {
+            day(start)?..=day(end)?
+        } else if let Some((start, end)) = s.split_once('-') {
+            day(start)?..=day(end)?
+        } else {
+            let d = day(s)?;
+            d..=d
+        };
+
+        if range.is_empty() {
+            return Err(format!("{s} is an empty range of days"));
+        }
+
+        Ok(Days(range))
+    }
+}
+
+type DayResult<T> = Result<T, Box<dyn Error>>;
+
+/// Everything `aoc` can do with one day, so the days only need listing once
+struct Commands {
+    run: fn(&InputSource, &str, Option<Part>, bool, Format) -> DayResult<Tally>,
+    bench: fn(&InputResolver, Option<Part>, usize) -> DayResult<Timings>,
+}
+
+fn commands<S: Solution>() -> Commands {
+    Commands {
+        run: run_day::<S>,
+        bench: bench_day::<S>,
+    }
+}
+
+fn solver(day: u8) -> Commands {
+    match day {
+        1 => commands::<day01::Day01>(),
+        2 => commands::<day02::Day02>(),
+        3 => commands::<day03::Day03>(),
+        4 => commands::<day04::Day04>(),
+        5 => commands::<day05::Day05>(),
+        6 => commands::<day06::Day06>(),
+        7 => commands::<day07::Day07>(),
+        8 => commands::<day08::Day08>(),
+        9 => commands::<day09::Day09>(),
+        10 => commands::<day10::Day10>(),
+        11 => commands::<day11::Day11>(),
+        12 => commands::<day12::Day12>(),
+        13 => commands::<day13::Day13>(),
+        14 => commands::<day14::Day14>(),
+        15 => commands::<day15::Day15>(),
+        16 => commands::<day16::Day16>(),
+        17 => commands::<day17::Day17>(),
+        18 => commands::<day18::Day18>(),
+        19 => commands::<day19::Day19>(),
+        20 => commands::<day20::Day20>(),
+        21 => commands::<day21::Day21>(),
+        22 => commands::<day22::Day22>(),
+        23 => commands::<day23::Day23>(),
+        24 => commands::<day24::Day24>(),
+        25 => commands::<day25::Day25>(),
+        _ => unreachable!("days are validated when the arguments are parsed"),
+    }
+}
+
+fn format_duration(d: Duration) -> String {
+    format!("{d:.2?}")
+}
+
+/// Time every requested day, printing a table as it goes and comparing against the baseline
+fn benchmark(args: &Args, resolver: &InputResolver) -> Result<(), Box<dyn Error>> {
+    let mut baseline = Baseline::load(&args.baseline)
+        .map_err(|e| format!("couldn't load {}: {e}", args.baseline.display()))?;
+    let threshold = args.threshold / 100.0;
+
+    println!(
+        "{:<4} {:<6} {:>10} {:>10} {:>10} {:>10}",
+        "day", "phase", "min", "median", "max", "baseline"
+    );
+
+    let mut results = vec![];
+    let mut regressions = 0;
+    for day in args.days.0.clone() {
+        let timings = match (solver(day).bench)(resolver, args.part, args.runs) {
+            Ok(timings) => timings,
+            Err(e) => {
+                eprintln!("day {day:02}: {e}");
+                continue;
+            }
+        };
+
+        for (phase, stats) in timings {
+            let base = baseline
+                .get(day, phase)
+                .map(format_duration)
+                .unwrap_or_else(|| "-".to_owned());
+            let regressed = baseline.regressed(day, phase, stats.median, threshold);
+            regressions += regressed as usize;
+
+            println!(
+                "{day:02}   {phase:<6} {:>10} {:>10} {:>10} {base:>10}{}",
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.max),
+                if regressed { "  REGRESSED" } else { "" },
+            );
+            results.push((day, phase, stats.median));
+        }
+    }
+
+    if args.save_baseline {
+        for (day, phase, median) in results {
+            baseline.set(day, phase, median);
+        }
+        baseline.save(&args.baseline)?;
+        println!("saved baseline to {}", args.baseline.display());
+    }
+
+    if regressions > 0 {
+        return Err(format!(
+            "{regressions} phases are more than {}% slower than the baseline",
+            args.threshold
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Download whatever's missing for every requested day, stopping at the first thing that goes wrong
+fn fetch(days: &Days) -> Result<(), Box<dyn Error>> {
+    let mut fetcher = Fetcher::from_env();
+    for day in days.0.clone() {
+        let dir = day_dir(day);
+        let input = fetcher.input(day, &dir)?;
+        let puzzle = fetcher.puzzle(day, &dir)?;
+        println!(
+            "day {day:02}: input {input}, puzzle {puzzle} in {}",
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+fn main() {
+    let args = Args::parse();
+    let multiple = args.days.0.start() != args.days.0.end();
+
+    if multiple && args.input.is_some() {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--input can only be used when running a single day",
+            )
+            .exit();
+    }
+
+    if args.fetch {
+        if let Err(e) = fetch(&args.days) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    let resolver = InputResolver::new(args.input.as_deref());
+    if args.bench {
+        if let Err(e) = benchmark(&args, &resolver) {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(header) = args.format.header() {
+        println!("{header}");
+    }
+
+    // with records, the day headings and totals would only get in the way of whatever reads them
+    let text = args.format == Format::Text;
+    let mut tally = Tally::default();
+    let mut errors = 0;
+    for day in args.days.0 {
+        if multiple && text {
+            println!("=== day {day:02} ===");
+        }
+
+        let result = resolver
+            .resolve(day)
+            .map_err(Box::from)
+            .and_then(|(source, input)| {
+                (solver(day).run)(&source, &input, args.part, args.record, args.format)
+            });
+        match result {
+            Ok(day_tally) => tally.merge(day_tally),
+            Err(e) => {
+                eprintln!("{e}");
+                errors += 1;
+            }
+        }
+    }
+
+    if multiple && text {
+        println!("=== {tally}, {errors} errors ===");
+    } else if multiple {
+        eprintln!("{tally}, {errors} errors");
+    }
+
+    if tally.fail > 0 || errors > 0 {
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Days;
+
+    #[test]
+    fn days() {
+        assert_eq!("7".parse(), Ok(Days(7..=7)));
+        assert_eq!("all".parse(), Ok(Days(1..=25)));
+        assert_eq!("3..9".parse(), Ok(Days(3..=9)));
+        assert_eq!("3..=9".parse(), Ok(Days(3..=9)));
+        assert_eq!("3-9".parse(), Ok(Days(3..=9)));
+
+        assert!("0".parse::<Days>().is_err());
+        assert!("26".parse::<Days>().is_err());
+        assert!("9..3".parse::<Days>().is_err());
+        assert!("seven".parse::<Days>().is_err());
+    }
+}
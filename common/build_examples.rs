@@ -0,0 +1,63 @@
+//! The build script every day shares. It writes a test for each part recorded in the answers file
+//! beside each `examples/NAME.txt`, for `common::examples!` to pull in. An example without
+//! answers just has to parse.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+/// Example names can be anything a file can be called, test names can't
+fn test_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("example_{name}")
+    } else {
+        name
+    }
+}
+
+fn main() {
+    // a directory is rescanned as a whole, so new examples get picked up too
+    println!("cargo:rerun-if-changed=examples");
+
+    let mut names: Vec<String> = fs::read_dir("examples")
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|file| !file.ends_with(".answers.txt"))
+        .filter_map(|file| Some(file.strip_suffix(".txt")?.to_owned()))
+        .collect();
+    names.sort();
+
+    let mut tests = String::new();
+    for name in names {
+        let answers =
+            fs::read_to_string(format!("examples/{name}.answers.txt")).unwrap_or_default();
+        let test = test_name(&name);
+        let mut parts = 0;
+        for (part, variant) in [(1, "One"), (2, "Two")] {
+            let header = format!("== part {part} ==");
+            if answers.lines().any(|line| line.trim() == header) {
+                parts += 1;
+                writeln!(
+                    tests,
+                    "#[test]\nfn {test}_part{part}() {{\n    \
+                     ::common::test::check_example::<Day>({name:?}, ::common::Part::{variant});\n}}"
+                )
+                .unwrap();
+            }
+        }
+        if parts == 0 {
+            writeln!(
+                tests,
+                "#[test]\nfn {test}_parses() {{\n    \
+                 ::common::test::check_example_parses::<Day>({name:?});\n}}"
+            )
+            .unwrap();
+        }
+    }
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(out, tests).unwrap();
+}
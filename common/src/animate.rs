@@ -0,0 +1,434 @@
+use std::{
+    fmt::Display,
+    io::{self, Stdout, Write},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+
+/// A simulation that can be played a step at a time. Going backwards means starting over and
+/// playing forwards again, so nothing has to keep a copy of every step.
+pub trait Playback {
+    /// Go back to how things were before the first step
+    fn restart(&mut self);
+    /// Move on a step, or return false if the simulation is already over
+    fn step(&mut self) -> bool;
+    /// The current state, as text
+    fn draw(&self) -> String;
+}
+
+/// A [Playback] made out of a way to set a simulation up and a way to move it on a step. The state
+/// only has to know how to print itself.
+pub struct Steps<S, Start, Step> {
+    start: Start,
+    step: Step,
+    state: S,
+}
+
+impl<S, Start, Step> Steps<S, Start, Step>
+where
+    S: Display,
+    Start: Fn() -> S,
+    Step: FnMut(&mut S) -> bool,
+{
+    pub fn new(start: Start, step: Step) -> Self {
+        let state = start();
+        Steps { start, step, state }
+    }
+}
+
+impl<S, Start, Step> Playback for Steps<S, Start, Step>
+where
+    S: Display,
+    Start: Fn() -> S,
+    Step: FnMut(&mut S) -> bool,
+{
+    fn restart(&mut self) {
+        self.state = (self.start)();
+    }
+
+    fn step(&mut self) -> bool {
+        (self.step)(&mut self.state)
+    }
+
+    fn draw(&self) -> String {
+        self.state.to_string()
+    }
+}
+
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+const MIN_DELAY: Duration = Duration::from_millis(1);
+const MAX_DELAY: Duration = Duration::from_secs(2);
+/// How far the view moves with each press of `w`, `a`, `s` or `d`
+const PAN: usize = 8;
+
+/// What the player wants to happen next
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Nothing besides redrawing
+    Wait,
+    /// Go to this step
+    Seek(usize),
+    Quit,
+}
+
+/// Keeps track of where playback is and turns key presses into commands
+#[derive(Debug)]
+pub struct Player {
+    /// The step being shown, where 0 is before anything has happened
+    pub step: usize,
+    /// The last step, once playback has run into it
+    pub end: Option<usize>,
+    pub paused: bool,
+    /// How long each step stays on screen while playing
+    pub delay: Duration,
+    /// How many columns and rows of the drawing are scrolled off the top left of the screen
+    pub offset: (usize, usize),
+    /// The step number typed in so far after pressing `g`
+    jump: Option<String>,
+}
+
+impl Default for Player {
+    fn default() -> Self {
+        Player {
+            step: 0,
+            end: None,
+            paused: false,
+            delay: DEFAULT_DELAY,
+            offset: (0, 0),
+            jump: None,
+        }
+    }
+}
+
+impl Player {
+    /// Handle a key press
+    pub fn key(&mut self, key: KeyCode) -> Command {
+        if let Some(jump) = &mut self.jump {
+            match key {
+                KeyCode::Char(c) if c.is_ascii_digit() => jump.push(c),
+                KeyCode::Backspace => {
+                    jump.pop();
+                }
+                KeyCode::Enter => {
+                    let target = jump.parse().ok();
+                    self.jump = None;
+                    if let Some(target) = target {
+                        self.paused = true;
+                        return Command::Seek(target);
+                    }
+                }
+                KeyCode::Esc => self.jump = None,
+                _ => {}
+            }
+            return Command::Wait;
+        }
+
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return Command::Quit,
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Right | KeyCode::Char('.') => {
+                self.paused = true;
+                return Command::Seek(self.step + 1);
+            }
+            KeyCode::Left | KeyCode::Char(',') => {
+                self.paused = true;
+                return Command::Seek(self.step.saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.delay = (self.delay / 2).max(MIN_DELAY);
+            }
+            KeyCode::Down | KeyCode::Char('-') => {
+                self.delay = (self.delay * 2).min(MAX_DELAY);
+            }
+            KeyCode::Char('a') => self.offset.0 = self.offset.0.saturating_sub(PAN),
+            KeyCode::Char('d') => self.offset.0 += PAN,
+            KeyCode::Char('w') => self.offset.1 = self.offset.1.saturating_sub(PAN),
+            KeyCode::Char('s') => self.offset.1 += PAN,
+            KeyCode::Char('g') => self.jump = Some(String::new()),
+            KeyCode::Home | KeyCode::Char('r') => return Command::Seek(0),
+            _ => {}
+        }
+        Command::Wait
+    }
+
+    /// What to do when no key has been pressed for a step's worth of time
+    pub fn tick(&self) -> Command {
+        if self.paused || self.jump.is_some() {
+            Command::Wait
+        } else {
+            Command::Seek(self.step + 1)
+        }
+    }
+
+    /// Move `playback` from the current step to `target`, stopping early if it runs out of steps.
+    /// Running into the end pauses playback there.
+    pub fn seek(&mut self, playback: &mut dyn Playback, target: usize) {
+        let target = self.end.map_or(target, |end| target.min(end));
+        if target < self.step {
+            playback.restart();
+            self.step = 0;
+        }
+
+        while self.step < target {
+            if !playback.step() {
+                self.end = Some(self.step);
+                break;
+            }
+            self.step += 1;
+        }
+
+        if self.end == Some(self.step) {
+            self.paused = true;
+        }
+    }
+
+    /// A line saying where playback is and which keys do what
+    pub fn status(&self) -> String {
+        if let Some(jump) = &self.jump {
+            return format!("jump to step: {jump}_  (enter to go, esc to cancel)");
+        }
+
+        let state = match (self.end == Some(self.step), self.paused) {
+            (true, _) => "end",
+            (false, true) => "paused",
+            (false, false) => "playing",
+        };
+        format!(
+            "step {} ({state}, {}ms)  space pause  \u{2190}\u{2192} step  +/- speed  wasd scroll  g jump  r restart  q quit",
+            self.step,
+            self.delay.as_millis()
+        )
+    }
+}
+
+/// As much of a drawing as fits in `width` by `height` characters, starting `offset` columns and
+/// rows in from the top left
+fn crop(drawing: &str, offset: (usize, usize), width: usize, height: usize) -> Vec<String> {
+    drawing
+        .lines()
+        .skip(offset.1)
+        .take(height)
+        .map(|line| line.chars().skip(offset.0).take(width).collect())
+        .collect()
+}
+
+/// The terminal in raw mode showing the alternate screen, put back how it was when dropped
+struct Screen {
+    out: Stdout,
+}
+
+impl Screen {
+    fn enter() -> io::Result<Screen> {
+        let mut out = io::stdout();
+        terminal::enable_raw_mode()?;
+        queue!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+        out.flush()?;
+        Ok(Screen { out })
+    }
+
+    fn draw(&mut self, playback: &dyn Playback, player: &Player) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+
+        // keep the bottom row for the status line
+        let lines = crop(
+            &playback.draw(),
+            player.offset,
+            width,
+            height.saturating_sub(1),
+        );
+        queue!(self.out, cursor::MoveTo(0, 0))?;
+        for line in lines {
+            queue!(
+                self.out,
+                Print(line),
+                terminal::Clear(ClearType::UntilNewLine),
+                Print("\r\n")
+            )?;
+        }
+
+        let status: String = player.status().chars().take(width).collect();
+        queue!(
+            self.out,
+            terminal::Clear(ClearType::FromCursorDown),
+            cursor::MoveTo(0, height.saturating_sub(1) as u16),
+            Print(status)
+        )?;
+        self.out.flush()
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        // there's nothing better to do with an error while putting the terminal back
+        let _ = queue!(self.out, cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = self.out.flush();
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Play a simulation in the terminal, redrawing it in place, until `q` is pressed
+pub fn play(playback: &mut dyn Playback) -> io::Result<()> {
+    let mut screen = Screen::enter()?;
+    let mut player = Player::default();
+
+    loop {
+        screen.draw(playback, &player)?;
+
+        // while paused there's nothing to do until a key comes along
+        let waiting = player.tick() == Command::Wait;
+        let event = if waiting || event::poll(player.delay)? {
+            Some(event::read()?)
+        } else {
+            None
+        };
+
+        let command = match event {
+            None => player.tick(),
+            Some(Event::Key(KeyEvent {
+                code: KeyCode::Char('c'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            })) => Command::Quit,
+            Some(Event::Key(KeyEvent {
+                code,
+                kind: KeyEventKind::Press,
+                ..
+            })) => player.key(code),
+            // anything else, like the terminal being resized, just needs a redraw
+            Some(_) => Command::Wait,
+        };
+
+        match command {
+            Command::Wait => {}
+            Command::Seek(target) => player.seek(playback, target),
+            Command::Quit => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::event::KeyCode;
+
+    use super::{crop, Command, Playback, Player, Steps};
+
+    /// Counts up to `max` and stops there
+    fn counter(max: usize) -> impl Playback {
+        Steps::new(
+            || 0,
+            move |n: &mut usize| {
+                if *n == max {
+                    false
+                } else {
+                    *n += 1;
+                    true
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn seeking() {
+        let mut playback = counter(10);
+        let mut player = Player::default();
+
+        player.seek(&mut playback, 4);
+        assert_eq!((player.step, playback.draw()), (4, "4".to_owned()));
+
+        // going back starts over and replays
+        player.seek(&mut playback, 2);
+        assert_eq!((player.step, playback.draw()), (2, "2".to_owned()));
+
+        // going past the end stops there and pauses
+        player.seek(&mut playback, 50);
+        assert_eq!(
+            (player.step, player.end, player.paused),
+            (10, Some(10), true)
+        );
+        assert_eq!(playback.draw(), "10");
+
+        // and once the end is known, nothing tries to go past it
+        assert_eq!(player.key(KeyCode::Right), Command::Seek(11));
+        player.seek(&mut playback, 11);
+        assert_eq!(player.step, 10);
+    }
+
+    #[test]
+    fn keys() {
+        let mut player = Player {
+            step: 5,
+            ..Default::default()
+        };
+
+        assert_eq!(player.tick(), Command::Seek(6));
+        assert_eq!(player.key(KeyCode::Char(' ')), Command::Wait);
+        assert!(player.paused);
+        assert_eq!(player.tick(), Command::Wait);
+
+        assert_eq!(player.key(KeyCode::Left), Command::Seek(4));
+        assert_eq!(player.key(KeyCode::Char('.')), Command::Seek(6));
+
+        let delay = player.delay;
+        player.key(KeyCode::Char('+'));
+        assert_eq!(player.delay, delay / 2);
+        player.key(KeyCode::Char('-'));
+        player.key(KeyCode::Char('-'));
+        assert_eq!(player.delay, delay * 2);
+
+        player.key(KeyCode::Char('d'));
+        player.key(KeyCode::Char('s'));
+        player.key(KeyCode::Char('w'));
+        player.key(KeyCode::Char('w'));
+        assert_eq!(player.offset, (8, 0));
+
+        assert_eq!(player.key(KeyCode::Char('q')), Command::Quit);
+    }
+
+    #[test]
+    fn jumping() {
+        let mut player = Player::default();
+        assert_eq!(player.key(KeyCode::Char('g')), Command::Wait);
+        for key in [
+            KeyCode::Char('1'),
+            KeyCode::Char('x'),
+            KeyCode::Char('2'),
+            KeyCode::Char('9'),
+            KeyCode::Backspace,
+        ] {
+            assert_eq!(player.key(key), Command::Wait);
+        }
+        assert!(player.status().contains("12_"));
+
+        // keys that would normally do something are just ignored while typing a step
+        assert_eq!(player.key(KeyCode::Char('q')), Command::Wait);
+        assert_eq!(player.key(KeyCode::Enter), Command::Seek(12));
+        assert!(player.paused);
+
+        // cancelling, or not typing anything, goes nowhere
+        player.key(KeyCode::Char('g'));
+        player.key(KeyCode::Char('3'));
+        assert_eq!(player.key(KeyCode::Esc), Command::Wait);
+        player.key(KeyCode::Char('g'));
+        assert_eq!(player.key(KeyCode::Enter), Command::Wait);
+        assert_eq!(player.key(KeyCode::Char('q')), Command::Quit);
+    }
+
+    #[test]
+    fn cropping() {
+        let drawing = "abcdef\nghijkl\nmnopqr\n";
+        assert_eq!(crop(drawing, (0, 0), 3, 2), vec!["abc", "ghi"]);
+        assert_eq!(crop(drawing, (4, 1), 3, 5), vec!["kl", "qr"]);
+        assert_eq!(
+            crop(drawing, (0, 0), 10, 10),
+            vec!["abcdef", "ghijkl", "mnopqr"]
+        );
+    }
+}
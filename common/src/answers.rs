@@ -0,0 +1,240 @@
+use std::{
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{error::ParseError, input::InputSource, Part};
+
+/// The known-good answers for one day's input, kept in a file beside the input. Each part gets a
+/// header line followed by the answer, which can span several lines (like day 10's CRT):
+///
+/// ```text
+/// == part 1 ==
+/// 13140
+/// == part 2 ==
+/// ##..##..
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// How a freshly computed answer compares to the recorded one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Pass => write!(f, "pass"),
+            Check::Fail { .. } => write!(f, "FAIL"),
+            Check::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Running totals of checks, for reporting across several parts or days
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    pub pass: usize,
+    pub fail: usize,
+    pub unknown: usize,
+}
+
+impl Tally {
+    pub fn add(&mut self, check: &Check) {
+        match check {
+            Check::Pass => self.pass += 1,
+            Check::Fail { .. } => self.fail += 1,
+            Check::Unknown => self.unknown += 1,
+        }
+    }
+
+    pub fn merge(&mut self, other: Tally) {
+        self.pass += other.pass;
+        self.fail += other.fail;
+        self.unknown += other.unknown;
+    }
+}
+
+impl Display for Tally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} unknown",
+            self.pass, self.fail, self.unknown
+        )
+    }
+}
+
+fn header(part: Part) -> &'static str {
+    match part {
+        Part::One => "== part 1 ==",
+        Part::Two => "== part 2 ==",
+    }
+}
+
+impl Answers {
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, part: Part, answer: impl Into<String>) {
+        let answer = Some(answer.into());
+        match part {
+            Part::One => self.part1 = answer,
+            Part::Two => self.part2 = answer,
+        }
+    }
+
+    /// Compare an answer against what's recorded for that part
+    pub fn check(&self, part: Part, actual: &str) -> Check {
+        match self.get(part) {
+            Some(expected) if expected == actual => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.to_owned(),
+            },
+            None => Check::Unknown,
+        }
+    }
+
+    /// Where the answers for an input live: `answers.txt` next to an `input.txt`, or
+    /// `dayNN.answers.txt` next to a flat `dayNN.txt`. Input from stdin has nowhere to keep them.
+    pub fn path_for(source: &InputSource) -> Option<PathBuf> {
+        let InputSource::File(path) = source else {
+            return None;
+        };
+
+        let name = match path.file_stem()?.to_str()? {
+            "input" => "answers.txt".to_owned(),
+            stem => format!("{stem}.answers.txt"),
+        };
+        Some(path.with_file_name(name))
+    }
+
+    /// Load the answers at `path`, where a missing file just means nothing is known yet
+    pub fn load(path: &Path) -> Result<Answers, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(s) => Ok(s.parse()?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+        let mut current: Option<(Part, Vec<&str>)> = None;
+
+        for line in s.lines() {
+            let part = [Part::One, Part::Two]
+                .into_iter()
+                .find(|&part| line.trim_end() == header(part));
+
+            match (part, &mut current) {
+                (Some(part), _) => {
+                    if let Some((prev, lines)) = current.replace((part, vec![])) {
+                        answers.set(prev, lines.join("\n"));
+                    }
+                }
+                (None, Some((_, lines))) => lines.push(line.trim_end()),
+                (None, None) if line.trim().is_empty() => {}
+                (None, None) => {
+                    return Err(ParseError::at(
+                        s,
+                        line,
+                        "expected a \"== part N ==\" header",
+                    ))
+                }
+            }
+        }
+
+        if let Some((part, lines)) = current {
+            answers.set(part, lines.join("\n"));
+        }
+
+        Ok(answers)
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for part in [Part::One, Part::Two] {
+            if let Some(answer) = self.get(part) {
+                writeln!(f, "{}\n{answer}", header(part))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::{input::InputSource, Part};
+
+    use super::{Answers, Check};
+
+    #[test]
+    fn round_trip() {
+        let answers = Answers {
+            part1: Some("13140".to_owned()),
+            part2: Some("##..\n#..#".to_owned()),
+        };
+        let text = answers.to_string();
+        assert_eq!(text, "== part 1 ==\n13140\n== part 2 ==\n##..\n#..#\n");
+        assert_eq!(text.parse(), Ok(answers));
+    }
+
+    #[test]
+    fn checks() {
+        let answers: Answers = "== part 1 ==\n24000\n".parse().unwrap();
+        assert_eq!(answers.check(Part::One, "24000"), Check::Pass);
+        assert_eq!(
+            answers.check(Part::One, "24001"),
+            Check::Fail {
+                expected: "24000".to_owned()
+            }
+        );
+        assert_eq!(answers.check(Part::Two, "45000"), Check::Unknown);
+    }
+
+    #[test]
+    fn garbage_is_an_error() {
+        let err = "24000\n== part 1 ==\n".parse::<Answers>().unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+
+    #[test]
+    fn lives_beside_the_input() {
+        let path = |p: &str| Answers::path_for(&InputSource::File(PathBuf::from(p)));
+        assert_eq!(
+            path("day07/input.txt"),
+            Some(PathBuf::from("day07/answers.txt"))
+        );
+        assert_eq!(
+            path("/inputs/day07.txt"),
+            Some(PathBuf::from("/inputs/day07.answers.txt"))
+        );
+        assert_eq!(Answers::path_for(&InputSource::Stdin), None);
+    }
+}
@@ -0,0 +1,219 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::Display,
+    fs,
+    hint::black_box,
+    io,
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use crate::{error::ParseError, Part, Solution};
+
+/// The separately timed steps of solving a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "part1"),
+            Phase::Part2 => write!(f, "part2"),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Part1),
+            "part2" => Ok(Phase::Part2),
+            _ => Err(format!("{s} is not a phase")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// How long each phase of a day took, in the order they ran
+pub type Timings = Vec<(Phase, Stats)>;
+
+/// Time `f` over `runs` runs (at least one)
+fn time<T>(runs: usize, mut f: impl FnMut() -> T) -> Stats {
+    let samples = (0..runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+
+    Stats::from_samples(samples)
+}
+
+/// Time each phase of a day separately over repeated runs. The parts are timed against a single
+/// parsed input, so they don't include the cost of parsing.
+pub fn bench<S: Solution>(
+    raw: &str,
+    part: Option<Part>,
+    runs: usize,
+) -> Result<Timings, ParseError> {
+    let input = S::parse(raw)?;
+
+    let mut results = vec![(Phase::Parse, time(runs, || S::parse(black_box(raw))))];
+    for part in Part::selected(part) {
+        let stats = match part {
+            Part::One => (Phase::Part1, time(runs, || S::part1(black_box(&input)))),
+            Part::Two => (Phase::Part2, time(runs, || S::part2(black_box(&input)))),
+        };
+        results.push(stats);
+    }
+
+    Ok(results)
+}
+
+/// Median timings from an earlier run, one `day phase nanoseconds` line per entry
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Baseline(pub BTreeMap<(u8, Phase), Duration>);
+
+impl Baseline {
+    /// Load the baseline at `path`, where a missing file is just an empty baseline
+    pub fn load(path: &Path) -> Result<Baseline, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(s) => Ok(s.parse()?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Baseline::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, day: u8, phase: Phase) -> Option<Duration> {
+        self.0.get(&(day, phase)).copied()
+    }
+
+    pub fn set(&mut self, day: u8, phase: Phase, median: Duration) {
+        self.0.insert((day, phase), median);
+    }
+
+    /// Whether `median` is more than `threshold` (a fraction, so 0.1 is 10%) slower than the
+    /// baseline. Anything without a baseline can't have regressed.
+    pub fn regressed(&self, day: u8, phase: Phase, median: Duration, threshold: f64) -> bool {
+        self.get(day, phase)
+            .is_some_and(|base| median.as_secs_f64() > base.as_secs_f64() * (1.0 + threshold))
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let entry = |line: &str| -> Option<((u8, Phase), Duration)> {
+            let mut words = line.split_whitespace();
+            let day = words.next()?.parse().ok()?;
+            let phase = words.next()?.parse().ok()?;
+            let nanos = words.next()?.parse().ok()?;
+            words
+                .next()
+                .is_none()
+                .then_some(((day, phase), Duration::from_nanos(nanos)))
+        };
+
+        s.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                entry(line).ok_or_else(|| {
+                    format!(
+                        "line {} of the baseline should be `day phase nanoseconds`, not {line:?}",
+                        idx + 1
+                    )
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Baseline)
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((day, phase), median) in &self.0 {
+            writeln!(f, "{day:02} {phase} {}", median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{Baseline, Phase, Stats};
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::from_samples(vec![ms(5), ms(1), ms(9), ms(3), ms(4)]);
+        assert_eq!(
+            stats,
+            Stats {
+                min: ms(1),
+                median: ms(4),
+                max: ms(9)
+            }
+        );
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.set(7, Phase::Parse, Duration::from_micros(12));
+        baseline.set(16, Phase::Part2, Duration::from_millis(800));
+
+        let text = baseline.to_string();
+        assert_eq!(text, "07 parse 12000\n16 part2 800000000\n");
+        assert_eq!(text.parse(), Ok(baseline));
+
+        assert!("07 parse".parse::<Baseline>().is_err());
+        assert!("07 parse 12 extra".parse::<Baseline>().is_err());
+    }
+
+    #[test]
+    fn regressions() {
+        let mut baseline = Baseline::default();
+        baseline.set(7, Phase::Part1, Duration::from_millis(100));
+
+        let ms = Duration::from_millis;
+        assert!(!baseline.regressed(7, Phase::Part1, ms(105), 0.1));
+        assert!(baseline.regressed(7, Phase::Part1, ms(120), 0.1));
+        assert!(!baseline.regressed(7, Phase::Part2, ms(120), 0.1));
+    }
+}
@@ -0,0 +1,234 @@
+use std::{
+    error::Error,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use clap::{error::ErrorKind, Parser};
+
+use crate::{
+    animate::play,
+    answers::{Answers, Check, Tally},
+    bench::{bench, Timings},
+    generate::seeded,
+    input::{InputResolver, InputSource},
+    render::{save, Frame},
+    report::{input_hash, Format, Record},
+    solve, Part, Solution,
+};
+
+#[derive(Parser)]
+#[command(about = "Run a single day of Advent of Code 2022")]
+struct DayArgs {
+    /// Path to the puzzle input, or `-` to read it from stdin. Defaults to `$AOC_INPUT_DIR`, then `dayNN/input.txt`
+    input: Option<String>,
+
+    /// Only run one part of the puzzle
+    #[arg(long)]
+    part: Option<Part>,
+
+    /// Save the computed answers as the known-good ones for this input
+    #[arg(long)]
+    record: bool,
+
+    /// Draw the simulation to a .gif, or to numbered .png or .ppm files. Draws part 1 unless `--part` says otherwise
+    #[arg(long, value_name = "PATH")]
+    render: Option<PathBuf>,
+
+    /// How many pixels wide each cell of the drawing is
+    #[arg(long, default_value_t = 4, requires = "render")]
+    scale: usize,
+
+    /// Play the simulation step by step in the terminal, taking keys from stdin. Plays part 1 unless `--part` says otherwise
+    #[arg(long, conflicts_with = "render")]
+    animate: bool,
+
+    /// Print a random input of about this size instead of solving anything
+    #[arg(long, value_name = "SIZE", conflicts_with_all = ["record", "render", "animate"])]
+    generate: Option<usize>,
+
+    /// The seed for `--generate`, so the same input can be made again
+    #[arg(long, default_value_t = 0, requires = "generate")]
+    seed: u64,
+
+    /// Print the answers as text, or as a JSON or CSV record per part
+    #[arg(long, default_value_t = Format::Text)]
+    format: Format,
+}
+
+fn print_answer(part: Part, answer: &str, check: &Check) {
+    // some answers (like day 10's CRT) are drawn over multiple lines, so start those on their own line
+    let separator = |s: &str| if s.contains('\n') { "\n" } else { " " };
+    println!(
+        "problem {part} score ({check}):{}{answer}",
+        separator(answer)
+    );
+
+    if let Check::Fail { expected } = check {
+        println!("  expected:{}{expected}", separator(expected));
+    }
+}
+
+/// Solve a day with `input`, read from `source`, checking each answer against the ones recorded
+/// beside that source, and print the answers in the given format. With `record`, the computed
+/// answers replace the recorded ones.
+pub fn run_day<S: Solution>(
+    source: &InputSource,
+    input: &str,
+    part: Option<Part>,
+    record: bool,
+    format: Format,
+) -> Result<Tally, Box<dyn Error>> {
+    let answers_path = Answers::path_for(source);
+    let mut known = match &answers_path {
+        Some(path) => Answers::load(path)
+            .map_err(|e| format!("couldn't load answers from {}: {e}", path.display()))?,
+        None => Answers::default(),
+    };
+
+    let timestamp = SystemTime::now();
+    let solved = solve::<S>(input, part)?;
+    let hash = match format {
+        Format::Text => String::new(),
+        Format::Json | Format::Csv => input_hash(input),
+    };
+
+    let mut tally = Tally::default();
+    for solved in &solved {
+        let check = known.check(solved.part, &solved.answer.text);
+        tally.add(&check);
+
+        let record = Record {
+            day: S::DAY,
+            part: solved.part,
+            answer: solved.answer.clone(),
+            check,
+            elapsed: solved.elapsed,
+            input_hash: hash.clone(),
+            timestamp,
+        };
+        match format {
+            Format::Text => print_answer(record.part, &record.answer.text, &record.check),
+            Format::Json => println!("{}", record.to_json()),
+            Format::Csv => println!("{}", record.to_csv()),
+        }
+    }
+
+    if record {
+        let path = answers_path.ok_or("there's nowhere to record answers for input from stdin")?;
+        for solved in solved {
+            known.set(solved.part, solved.answer.text);
+        }
+        known.save(&path)?;
+        // keep stdout to just the records when something else is reading them
+        let message = format!("recorded answers in {}", path.display());
+        match format {
+            Format::Text => println!("{message}"),
+            Format::Json | Format::Csv => eprintln!("{message}"),
+        }
+    }
+
+    Ok(tally)
+}
+
+/// Time a day with the input the resolver finds, over `runs` runs of each phase
+pub fn bench_day<S: Solution>(
+    resolver: &InputResolver,
+    part: Option<Part>,
+    runs: usize,
+) -> Result<Timings, Box<dyn Error>> {
+    let input = resolver.read(S::DAY)?;
+    Ok(bench::<S>(&input, part, runs)?)
+}
+
+/// Draw a part of a day with `raw` as the input, saving the frames to `path`. Says where they went
+/// on stdout for text output, and on stderr when stdout is for records.
+pub fn render_day<S: Solution>(
+    raw: &str,
+    part: Part,
+    path: &Path,
+    scale: usize,
+    format: Format,
+) -> Result<(), Box<dyn Error>> {
+    let input = S::parse(raw)?;
+    let frames = S::render(&input, part)
+        .ok_or_else(|| format!("day {} doesn't have anything to render", S::DAY))?;
+
+    let frames: Vec<Frame> = frames.iter().map(|f| f.scale(scale)).collect();
+    let message = match save(&frames, path)?.as_slice() {
+        [first, .., last] => format!(
+            "rendered part {part} to {} through {}",
+            first.display(),
+            last.display()
+        ),
+        [file] => format!(
+            "rendered {} frames of part {part} to {}",
+            frames.len(),
+            file.display()
+        ),
+        [] => return Ok(()),
+    };
+    match format {
+        Format::Text => println!("{message}"),
+        Format::Json | Format::Csv => eprintln!("{message}"),
+    }
+
+    Ok(())
+}
+
+/// Play a part of a day with `raw` as the input in the terminal, until it's quit
+pub fn animate_day<S: Solution>(raw: &str, part: Part) -> Result<(), Box<dyn Error>> {
+    let input = S::parse(raw)?;
+    let mut playback = S::animate(&input, part)
+        .ok_or_else(|| format!("day {} doesn't have anything to animate", S::DAY))?;
+    play(playback.as_mut())?;
+    Ok(())
+}
+
+/// The whole `main` for a day's binary. Exits with an error if any answer doesn't match the
+/// recorded one.
+pub fn main<S: Solution>() {
+    let args = DayArgs::parse();
+    if let Some(size) = args.generate {
+        print!("{}", S::generate(&mut seeded(args.seed), size));
+        return;
+    }
+
+    // the animation takes its keys from stdin, so the input can't be there too
+    if args.animate && args.input.as_deref() == Some("-") {
+        clap::Error::raw(
+            ErrorKind::ArgumentConflict,
+            "--animate reads keys from stdin, so the input can't come from there too\n",
+        )
+        .exit();
+    }
+
+    if let Some(header) = args.format.header() {
+        println!("{header}");
+    }
+
+    // stdin can only be read once, so everything shares the one copy of the input
+    let result = InputResolver::new(args.input.as_deref())
+        .resolve(S::DAY)
+        .map_err(Box::<dyn Error>::from)
+        .and_then(|(source, input)| {
+            let tally = run_day::<S>(&source, &input, args.part, args.record, args.format)?;
+            if let Some(path) = &args.render {
+                let part = args.part.unwrap_or(Part::One);
+                render_day::<S>(&input, part, path, args.scale, args.format)?;
+            }
+            if args.animate {
+                animate_day::<S>(&input, args.part.unwrap_or(Part::One))?;
+            }
+            Ok(tally)
+        });
+
+    match result {
+        Ok(tally) if tally.fail > 0 => std::process::exit(1),
+        Ok(_) => {}
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    ops::{Add, Mul, Sub},
+};
+
+/// Where a sequence starts repeating, and how often. Steps are counted from 0, so step
+/// `start + length` looks just like step `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step that looks just like step `n`
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+
+    /// How many whole times the cycle repeats between `reduce(n)` and `n`
+    pub fn repeats(&self, n: usize) -> usize {
+        n.saturating_sub(self.start) / self.length
+    }
+}
+
+/// Anything measured along a sequence that grows by the same amount every time round the cycle
+pub trait Measure:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + TryFrom<usize>
+{
+}
+
+impl<V> Measure for V where
+    V: Copy + Add<Output = V> + Sub<Output = V> + Mul<Output = V> + TryFrom<usize>
+{
+}
+
+/// A sequence of measurements that's been followed far enough to see it repeat, so it can be
+/// extrapolated as far as you like
+#[derive(Debug, Clone)]
+pub struct Periodic<V> {
+    pub cycle: Cycle,
+    /// The value after each step, up to and including the first repeat
+    pub values: Vec<V>,
+}
+
+impl<V: Measure> Periodic<V> {
+    /// The value after step `n`, which can be as far past the end of `values` as you like
+    pub fn value_at(&self, n: usize) -> V {
+        if n < self.values.len() {
+            return self.values[n];
+        }
+
+        let Cycle { start, length } = self.cycle;
+        let per_cycle = self.values[start + length] - self.values[start];
+        let repeats = V::try_from(self.cycle.repeats(n))
+            .unwrap_or_else(|_| panic!("{n} steps is too far to extrapolate"));
+
+        self.values[self.cycle.reduce(n)] + per_cycle * repeats
+    }
+}
+
+/// Follow `steps`, each a key describing the state after that step and the value measured there,
+/// until a key comes round again. Every key is remembered, so this finds the cycle as soon as it
+/// starts repeating. Returns None if the steps run out first.
+pub fn detect<K, V>(steps: impl IntoIterator<Item = (K, V)>) -> Option<Periodic<V>>
+where
+    K: Eq + Hash,
+{
+    let mut seen: HashMap<K, usize> = HashMap::new();
+    let mut values = vec![];
+
+    for (step, (key, value)) in steps.into_iter().enumerate() {
+        values.push(value);
+        if let Some(start) = seen.insert(key, step) {
+            let cycle = Cycle {
+                start,
+                length: step - start,
+            };
+            return Some(Periodic { cycle, values });
+        }
+    }
+
+    None
+}
+
+/// Brent's algorithm: find the cycle in the keys from `sequence` while only holding on to a
+/// couple of them at a time, for when remembering every key would take too much memory. The
+/// sequence gets replayed from the start, so `sequence` has to produce the same keys every call.
+pub fn brent<K, I>(mut sequence: impl FnMut() -> I) -> Option<Cycle>
+where
+    K: PartialEq,
+    I: Iterator<Item = K>,
+{
+    // find the length by racing ahead and moving the tortoise up every power of two
+    let mut hare = sequence();
+    let mut tortoise = hare.next()?;
+    let mut power = 1;
+    let mut length = 1;
+    let mut key = hare.next()?;
+    while tortoise != key {
+        if power == length {
+            tortoise = key;
+            power *= 2;
+            length = 0;
+        }
+        key = hare.next()?;
+        length += 1;
+    }
+
+    Some(Cycle {
+        start: cycle_start(sequence, length)?,
+        length,
+    })
+}
+
+/// Floyd's tortoise and hare: the same as `brent`, but with the classic two-speed race
+pub fn floyd<K, I>(mut sequence: impl FnMut() -> I) -> Option<Cycle>
+where
+    K: PartialEq,
+    I: Iterator<Item = K>,
+{
+    let mut tortoise = sequence();
+    let mut hare = sequence();
+    hare.next()?;
+
+    // the hare moves two steps for every one of the tortoise's, until they land on matching keys
+    let (mut t, mut h) = (tortoise.next()?, hare.next()?);
+    while t != h {
+        t = tortoise.next()?;
+        hare.next()?;
+        h = hare.next()?;
+    }
+
+    // now walk round the cycle once to measure it
+    let mut length = 1;
+    let mut next = tortoise.next()?;
+    while next != t {
+        next = tortoise.next()?;
+        length += 1;
+    }
+
+    Some(Cycle {
+        start: cycle_start(sequence, length)?,
+        length,
+    })
+}
+
+/// Once the length is known, a pointer that starts `length` ahead meets one from the very start
+/// right where the cycle begins
+fn cycle_start<K, I>(mut sequence: impl FnMut() -> I, length: usize) -> Option<usize>
+where
+    K: PartialEq,
+    I: Iterator<Item = K>,
+{
+    let mut behind = sequence();
+    let mut ahead = sequence();
+    let mut ahead_key = ahead.nth(length)?;
+
+    let mut start = 0;
+    while behind.next()? != ahead_key {
+        ahead_key = ahead.next()?;
+        start += 1;
+    }
+
+    Some(start)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{brent, detect, floyd, Cycle};
+
+    /// keys 0, 1, 2, then 3 through 7 over and over, with the value climbing by 10 each time round
+    fn sequence() -> impl Iterator<Item = (u32, u64)> {
+        (0..).map(|n: u64| {
+            let key = if n < 3 {
+                n as u32
+            } else {
+                3 + (n as u32 - 3) % 5
+            };
+            (key, n * 2)
+        })
+    }
+
+    #[test]
+    fn detects_and_extrapolates() {
+        let periodic = detect(sequence()).unwrap();
+        assert_eq!(
+            periodic.cycle,
+            Cycle {
+                start: 3,
+                length: 5
+            }
+        );
+
+        for n in [0, 2, 3, 7, 8, 9, 1_000, 1_000_000_000_000] {
+            assert_eq!(periodic.value_at(n as usize), n * 2);
+        }
+    }
+
+    #[test]
+    fn no_cycle() {
+        assert!(detect((0..10).map(|n| (n, n))).is_none());
+    }
+
+    #[test]
+    fn brent_and_floyd_agree() {
+        let keys = || sequence().map(|(key, _)| key);
+        let expected = Some(Cycle {
+            start: 3,
+            length: 5,
+        });
+        assert_eq!(brent(keys), expected);
+        assert_eq!(floyd(keys), expected);
+
+        // a cycle right from the start, and one that's a single step long
+        assert_eq!(
+            brent(|| [1, 2].into_iter().cycle()),
+            Some(Cycle {
+                start: 0,
+                length: 2
+            })
+        );
+        assert_eq!(
+            floyd(|| [1, 2, 3].into_iter().chain([4].into_iter().cycle())),
+            Some(Cycle {
+                start: 3,
+                length: 1
+            })
+        );
+    }
+
+    #[test]
+    fn reduce() {
+        let cycle = Cycle {
+            start: 3,
+            length: 5,
+        };
+        assert_eq!(cycle.reduce(2), 2);
+        assert_eq!(cycle.reduce(8), 3);
+        assert_eq!(cycle.reduce(14), 4);
+        assert_eq!(cycle.repeats(14), 2);
+    }
+}
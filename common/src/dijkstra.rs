@@ -1,69 +0,0 @@
-use std::{cmp::Ordering, collections::BinaryHeap};
-
-#[derive(Debug)]
-pub struct Edge {
-    pub node: usize,
-    pub cost: usize,
-}
-#[derive(Copy, Clone, Eq, PartialEq)]
-pub struct State {
-    pub cost: usize,
-    pub position: usize,
-}
-
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| self.position.cmp(&other.position))
-    }
-}
-
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-type GridIndex = usize;
-
-pub fn shortest_path(
-    adj_list: &Vec<Vec<Edge>>,
-    start: GridIndex,
-    goal: GridIndex,
-) -> Option<usize> {
-    let mut dist: Vec<_> = (0..adj_list.len()).map(|_| usize::MAX).collect();
-
-    let mut heap = BinaryHeap::new();
-
-    dist[start] = 0;
-    heap.push(State {
-        cost: 0,
-        position: start,
-    });
-
-    while let Some(State { cost, position }) = heap.pop() {
-        if position == goal {
-            return Some(cost);
-        }
-
-        if cost > dist[position] {
-            continue;
-        }
-
-        for edge in &adj_list[position] {
-            let next = State {
-                cost: cost + edge.cost,
-                position: edge.node,
-            };
-
-            if next.cost < dist[next.position] {
-                heap.push(next);
-                dist[next.position] = next.cost;
-            }
-        }
-    }
-
-    None
-}
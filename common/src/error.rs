@@ -0,0 +1,149 @@
+use std::{error::Error, fmt::Display};
+
+use nom::{
+    error::{VerboseError, VerboseErrorKind},
+    Offset,
+};
+
+/// A parse failure pinned to a spot in the puzzle input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number of the failure
+    pub line: usize,
+    /// 1-based column (in characters) of the failure
+    pub column: usize,
+    /// The whole line that the failure happened on
+    pub text: String,
+    /// What went wrong at that spot
+    pub message: String,
+    /// The `context` labels the failure happened inside of, outermost first
+    pub context: Vec<String>,
+}
+
+impl ParseError {
+    /// Build an error for `remaining`, which must be a suffix of `input`
+    pub fn at(input: &str, remaining: &str, message: impl Into<String>) -> ParseError {
+        let offset = input.offset(remaining);
+        let before = &input[..offset];
+
+        let line_start = before.rfind('\n').map(|x| x + 1).unwrap_or(0);
+        let line_end = input[offset..]
+            .find('\n')
+            .map(|x| x + offset)
+            .unwrap_or(input.len());
+
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_owned(),
+            message: message.into(),
+            context: vec![],
+        }
+    }
+
+    /// Build an error for the start of a 1-based `line` of `input`, for problems found after
+    /// parsing that are about a whole line. Line 0 points at the start of the input, and a line
+    /// past the end at the end of it.
+    pub fn on_line(input: &str, line: usize, message: impl Into<String>) -> ParseError {
+        let remaining = match line.checked_sub(1) {
+            None => input,
+            Some(idx) => input.lines().nth(idx).unwrap_or(&input[input.len()..]),
+        };
+        ParseError::at(input, remaining, message)
+    }
+
+    /// Convert a nom error into a located error. The first entry nom records is the innermost
+    /// failure, and the context labels get added on the way back out.
+    pub fn from_verbose(input: &str, error: VerboseError<&str>) -> ParseError {
+        let Some((remaining, kind)) = error.errors.first() else {
+            return ParseError::at(input, input, "unknown parse error");
+        };
+
+        let message = match kind {
+            VerboseErrorKind::Char(c) => format!("expected '{c}'"),
+            VerboseErrorKind::Nom(kind) => {
+                format!("{} parser failed", kind.description().to_lowercase())
+            }
+            VerboseErrorKind::Context(context) => format!("couldn't parse {context}"),
+        };
+
+        let mut result = ParseError::at(input, remaining, message);
+        result.context = error
+            .errors
+            .iter()
+            .rev()
+            .filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(context) => Some(context.to_string()),
+                _ => None,
+            })
+            .collect();
+
+        result
+    }
+
+    /// Move this error down by `lines`, for when the text it was built from was one piece of a
+    /// larger input
+    pub fn offset_lines(mut self, lines: usize) -> ParseError {
+        self.line += lines;
+        self
+    }
+
+    /// The text from the point of failure to the end of the line
+    pub fn found(&self) -> &str {
+        let start = self
+            .text
+            .char_indices()
+            .nth(self.column - 1)
+            .map(|(idx, _)| idx)
+            .unwrap_or(self.text.len());
+
+        &self.text[start..]
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "parse error at line {}, column {}",
+            self.line, self.column
+        )?;
+        if !self.context.is_empty() {
+            write!(f, " (in {})", self.context.join(" > "))?;
+        }
+
+        let found = match self.found() {
+            "" => "end of line",
+            found => found,
+        };
+        writeln!(f, ": {} but found \"{found}\"", self.message)?;
+
+        // point at the exact spot on the offending line
+        let gutter = self.line.to_string().len();
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(f, "{:gutter$} | {:>column$}", "", "^", column = self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod test {
+    use super::ParseError;
+
+    #[test]
+    fn on_line() {
+        let input = "first\nsecond\nthird";
+        let at = |line| {
+            let e = ParseError::on_line(input, line, "wrong");
+            (e.line, e.column, e.text)
+        };
+        assert_eq!(at(2), (2, 1, "second".to_owned()));
+        assert_eq!(at(3), (3, 1, "third".to_owned()));
+        // no line at all is the start, and past the end is the end
+        assert_eq!(at(0), (1, 1, "first".to_owned()));
+        assert_eq!(at(7), (3, 6, "third".to_owned()));
+    }
+}
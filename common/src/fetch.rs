@@ -0,0 +1,378 @@
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// The environment variable holding the value of the `session` cookie from a logged in browser
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The environment variable that points the fetcher at another server, like a local stub
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The least time left between two requests, so fetching every day doesn't hammer the site
+pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(2);
+
+const YEAR: u16 = 2022;
+
+/// What fetching a file came down to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Downloaded,
+    /// It was already there, so nothing was asked for
+    Cached,
+}
+
+impl Display for Fetched {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fetched::Downloaded => write!(f, "downloaded"),
+            Fetched::Cached => write!(f, "cached"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// Something has to be downloaded, and there's no session to do it with
+    NoSession,
+    /// The server answered, but not with the file
+    Status {
+        url: String,
+        status: u16,
+    },
+    /// The server couldn't be reached at all
+    Transport {
+        url: String,
+        message: String,
+    },
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+}
+
+impl Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::NoSession => write!(
+                f,
+                "set {SESSION_VAR} to the session cookie from a logged in browser to download anything"
+            ),
+            FetchError::Status { url, status: 400 } => {
+                write!(f, "{url} refused the session (400), it may have expired")
+            }
+            FetchError::Status { url, status: 404 } => {
+                write!(f, "{url} isn't there (404), the puzzle may not be out yet")
+            }
+            FetchError::Status { url, status: 429 } => {
+                write!(f, "{url} says to slow down (429), try again later")
+            }
+            FetchError::Status { url, status } => write!(f, "{url} answered with {status}"),
+            FetchError::Transport { url, message } => write!(f, "couldn't reach {url}: {message}"),
+            FetchError::Io { path, error } => write!(f, "couldn't write {}: {error}", path.display()),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+/// Downloads puzzle inputs and text into the day folders, once each. Every request waits until
+/// at least `interval` has passed since the one before.
+pub struct Fetcher {
+    base_url: String,
+    session: Option<String>,
+    interval: Duration,
+    last_request: Option<Instant>,
+    agent: ureq::Agent,
+}
+
+impl Fetcher {
+    /// A fetcher for a server like the real one at `base_url`. Without a session it can only find
+    /// what's already cached.
+    pub fn new(base_url: &str, session: Option<String>) -> Fetcher {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session,
+            interval: DEFAULT_INTERVAL,
+            last_request: None,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(concat!("aoc-2022-fetcher/", env!("CARGO_PKG_VERSION")))
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// A fetcher for the base URL and session the environment gives, if any
+    pub fn from_env() -> Fetcher {
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+        let session = env::var(SESSION_VAR).ok().filter(|s| !s.trim().is_empty());
+        Fetcher::new(&base_url, session)
+    }
+
+    /// Wait this long between requests instead of [DEFAULT_INTERVAL]
+    pub fn with_interval(self, interval: Duration) -> Fetcher {
+        Fetcher { interval, ..self }
+    }
+
+    /// Save a day's input to `input.txt` in `dir`, unless it's already there
+    pub fn input(&mut self, day: u8, dir: &Path) -> Result<Fetched, FetchError> {
+        let url = format!("{}/{YEAR}/day/{day}/input", self.base_url);
+        self.cached(&url, &dir.join("input.txt"))
+    }
+
+    /// Save a day's puzzle page to `puzzle.html` in `dir`, unless it's already there. It only has
+    /// part 2 in it if part 1 was solved before it was fetched, so delete it to fetch it again.
+    pub fn puzzle(&mut self, day: u8, dir: &Path) -> Result<Fetched, FetchError> {
+        let url = format!("{}/{YEAR}/day/{day}", self.base_url);
+        self.cached(&url, &dir.join("puzzle.html"))
+    }
+
+    fn cached(&mut self, url: &str, path: &Path) -> Result<Fetched, FetchError> {
+        if path.exists() {
+            return Ok(Fetched::Cached);
+        }
+
+        let body = self.get(url)?;
+        let io_error = |error| FetchError::Io {
+            path: path.to_owned(),
+            error,
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+
+        // write it all somewhere else first, so an interrupted download isn't taken for a cached
+        // file next time
+        let mut partial = path.as_os_str().to_owned();
+        partial.push(format!(".{}.part", std::process::id()));
+        let partial = PathBuf::from(partial);
+        fs::write(&partial, body)
+            .and_then(|()| fs::rename(&partial, path))
+            .map_err(|error| {
+                let _ = fs::remove_file(&partial);
+                io_error(error)
+            })?;
+        Ok(Fetched::Downloaded)
+    }
+
+    fn get(&mut self, url: &str) -> Result<String, FetchError> {
+        let session = self.session.as_deref().ok_or(FetchError::NoSession)?;
+
+        if let Some(last) = self.last_request {
+            thread::sleep(self.interval.saturating_sub(last.elapsed()));
+        }
+        self.last_request = Some(Instant::now());
+
+        let transport = |message: String| FetchError::Transport {
+            url: url.to_owned(),
+            message,
+        };
+        match self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={session}"))
+            .call()
+        {
+            Ok(response) => response.into_string().map_err(|e| transport(e.to_string())),
+            Err(ureq::Error::Status(status, _)) => Err(FetchError::Status {
+                url: url.to_owned(),
+                status,
+            }),
+            // its own message starts with the URL again, so just take why it failed
+            Err(ureq::Error::Transport(e)) => Err(transport(match e.source() {
+                Some(source) => format!("{}: {source}", e.kind()),
+                None => e.kind().to_string(),
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        fs,
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        path::PathBuf,
+        sync::{Arc, Mutex},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::{FetchError, Fetched, Fetcher};
+
+    /// A request the stub server was sent: the path, the cookie, and when it came in
+    type Request = (String, String, Instant);
+
+    /// A server on a free local port that answers every request with whatever `respond` makes of
+    /// its path, and keeps a note of each request
+    fn stub(respond: fn(&str) -> (u16, String)) -> (String, Arc<Mutex<Vec<Request>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let seen = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let path = line.split(' ').nth(1).unwrap_or_default().to_owned();
+                let mut cookie = String::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("cookie") {
+                            cookie = value.trim().to_owned();
+                        }
+                    }
+                }
+                seen.lock()
+                    .unwrap()
+                    .push((path.clone(), cookie, Instant::now()));
+
+                let (status, body) = respond(&path);
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Whatever\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (url, requests)
+    }
+
+    fn puzzle_site(path: &str) -> (u16, String) {
+        match path {
+            "/2022/day/7/input" => (200, "$ cd /\n$ ls\n".to_owned()),
+            "/2022/day/7" => (200, "<article>No Space Left On Device</article>".to_owned()),
+            _ => (404, "Not Found".to_owned()),
+        }
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn fetcher(url: &str) -> Fetcher {
+        Fetcher::new(url, Some("cookie".to_owned())).with_interval(Duration::ZERO)
+    }
+
+    #[test]
+    fn downloads_once() {
+        let (url, requests) = stub(puzzle_site);
+        let dir = scratch_dir("once").join("day07");
+        let mut first = fetcher(&url);
+
+        assert_eq!(first.input(7, &dir).unwrap(), Fetched::Downloaded);
+        assert_eq!(first.puzzle(7, &dir).unwrap(), Fetched::Downloaded);
+        assert_eq!(
+            fs::read_to_string(dir.join("input.txt")).unwrap(),
+            "$ cd /\n$ ls\n"
+        );
+        assert!(fs::read_to_string(dir.join("puzzle.html"))
+            .unwrap()
+            .contains("No Space Left"));
+
+        // a fresh fetcher still finds them cached, and doesn't ask again
+        let mut again = fetcher(&url);
+        assert_eq!(again.input(7, &dir).unwrap(), Fetched::Cached);
+        assert_eq!(again.puzzle(7, &dir).unwrap(), Fetched::Cached);
+
+        let requests = requests.lock().unwrap();
+        let paths: Vec<&str> = requests.iter().map(|(path, _, _)| path.as_str()).collect();
+        assert_eq!(paths, ["/2022/day/7/input", "/2022/day/7"]);
+        assert!(requests
+            .iter()
+            .all(|(_, cookie, _)| cookie == "session=cookie"));
+
+        // and nothing was left half written alongside them
+        let mut files: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        files.sort();
+        assert_eq!(files, ["input.txt", "puzzle.html"]);
+
+        fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn errors_leave_nothing_behind() {
+        let (url, _) = stub(puzzle_site);
+        let dir = scratch_dir("missing").join("day08");
+
+        let err = fetcher(&url).input(8, &dir).unwrap_err();
+        assert!(
+            matches!(err, FetchError::Status { status: 404, .. }),
+            "{err}"
+        );
+        assert!(!dir.join("input.txt").exists());
+    }
+
+    #[test]
+    fn needs_a_session() {
+        let (url, requests) = stub(puzzle_site);
+        let dir = scratch_dir("session").join("day07");
+
+        let err = Fetcher::new(&url, None).input(7, &dir).unwrap_err();
+        assert!(matches!(err, FetchError::NoSession));
+        assert!(requests.lock().unwrap().is_empty());
+
+        // but cached files don't need one
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("input.txt"), "cached").unwrap();
+        assert_eq!(
+            Fetcher::new(&url, None).input(7, &dir).unwrap(),
+            Fetched::Cached
+        );
+        fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn waits_between_requests() {
+        let (url, requests) = stub(puzzle_site);
+        let dir = scratch_dir("slow").join("day07");
+        let interval = Duration::from_millis(200);
+        let mut fetcher = Fetcher::new(&url, Some("cookie".to_owned())).with_interval(interval);
+
+        fetcher.input(7, &dir).unwrap();
+        fetcher.puzzle(7, &dir).unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[1].2 - requests[0].2 >= interval);
+        fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn unreachable() {
+        // nothing listens on a port that was just given back
+        let port = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let dir = scratch_dir("unreachable");
+        let err = fetcher(&format!("http://127.0.0.1:{port}"))
+            .input(7, &dir)
+            .unwrap_err();
+        assert!(matches!(err, FetchError::Transport { .. }), "{err}");
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::HashSet;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// The random number generator every day's generator is given, so the same seed always makes the
+/// same input
+pub fn seeded(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// `count` different names, each `len` letters picked from `alphabet`. There have to be enough
+/// combinations to go round.
+pub fn names(rng: &mut impl Rng, count: usize, len: usize, alphabet: &str) -> Vec<String> {
+    let letters: Vec<char> = alphabet.chars().collect();
+    assert!(
+        (letters.len() as f64).powi(len as i32) >= count as f64,
+        "there aren't {count} different names of {len} letters from {alphabet}"
+    );
+
+    let mut seen = HashSet::new();
+    let mut names = Vec::with_capacity(count);
+    while names.len() < count {
+        let name: String = (0..len)
+            .map(|_| letters[rng.gen_range(0..letters.len())])
+            .collect();
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// A grid of characters, a line per row
+pub fn grid(width: usize, height: usize, mut cell: impl FnMut(usize, usize) -> char) -> String {
+    let mut s = String::with_capacity((width + 1) * height);
+    for y in 0..height {
+        s.extend((0..width).map(|x| cell(x, y)));
+        s.push('\n');
+    }
+    s
+}
+
+/// Every item on its own line
+pub fn lines<T: ToString>(items: impl IntoIterator<Item = T>) -> String {
+    items
+        .into_iter()
+        .map(|item| item.to_string() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use super::{grid, lines, names, seeded};
+
+    #[test]
+    fn seeds_repeat() {
+        let a = names(&mut seeded(7), 20, 2, "ABCDEF");
+        assert_eq!(a, names(&mut seeded(7), 20, 2, "ABCDEF"));
+        assert_ne!(a, names(&mut seeded(8), 20, 2, "ABCDEF"));
+    }
+
+    #[test]
+    fn unique_names() {
+        // every possible name, so it has to find all of them
+        let all = names(&mut seeded(1), 9, 2, "abc");
+        assert_eq!(all.iter().collect::<HashSet<_>>().len(), 9);
+        assert!(all.iter().all(|n| n.len() == 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_names() {
+        names(&mut seeded(1), 10, 2, "abc");
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            grid(3, 2, |x, y| if x == y { '#' } else { '.' }),
+            "#..\n.#.\n"
+        );
+        assert_eq!(lines([1, 2, 3]), "1\n2\n3\n");
+    }
+}
@@ -0,0 +1,220 @@
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// The environment variable that points at a directory of puzzle inputs, laid out either as
+/// `dayNN/input.txt` (like this repo) or as flat `dayNN.txt` files
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl From<&str> for InputSource {
+    fn from(value: &str) -> Self {
+        match value {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl InputSource {
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut s = String::new();
+                io::stdin().read_to_string(&mut s)?;
+                Ok(s)
+            }
+            InputSource::File(path) => fs::read_to_string(path),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub day: u8,
+    /// Every source that was tried, in order, along with why it couldn't be read
+    pub tried: Vec<(InputSource, io::Error)>,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "couldn't read the input for day {:02}, tried:", self.day)?;
+        for (source, err) in &self.tried {
+            write!(f, "\n  {source}: {err}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for InputError {}
+
+/// Figures out where a day's input lives. In order, it tries:
+///
+/// 1. an explicit path given on the command line (`-` means stdin), and nothing else if there is one
+/// 2. the directory named by `AOC_INPUT_DIR`
+/// 3. the existing `dayNN/input.txt` convention, relative to the current directory, its parent
+///    (for running from inside a day folder), and the workspace this was built from
+#[derive(Debug, Clone, Default)]
+pub struct InputResolver {
+    pub explicit: Option<InputSource>,
+    pub input_dir: Option<PathBuf>,
+    pub search_roots: Vec<PathBuf>,
+}
+
+impl InputResolver {
+    /// Build a resolver from an optional command line argument and the environment
+    pub fn new(explicit: Option<&str>) -> InputResolver {
+        InputResolver {
+            explicit: explicit.map(InputSource::from),
+            input_dir: env::var_os(INPUT_DIR_VAR).map(PathBuf::from),
+            search_roots: vec![PathBuf::from("."), PathBuf::from(".."), workspace()],
+        }
+    }
+
+    pub fn candidates(&self, day: u8) -> Vec<InputSource> {
+        if let Some(explicit) = &self.explicit {
+            return vec![explicit.clone()];
+        }
+
+        let folder = format!("day{day:02}");
+        let mut candidates = vec![];
+        if let Some(dir) = &self.input_dir {
+            candidates.push(InputSource::File(dir.join(&folder).join("input.txt")));
+            candidates.push(InputSource::File(dir.join(format!("{folder}.txt"))));
+        }
+
+        for root in &self.search_roots {
+            candidates.push(InputSource::File(root.join(&folder).join("input.txt")));
+        }
+
+        candidates
+    }
+
+    /// Read the first candidate that works, or report every one that didn't
+    pub fn read(&self, day: u8) -> Result<String, InputError> {
+        self.resolve(day).map(|(_, input)| input)
+    }
+
+    /// Like `read`, but also say which candidate the input came from
+    pub fn resolve(&self, day: u8) -> Result<(InputSource, String), InputError> {
+        let mut tried = vec![];
+        for source in self.candidates(day) {
+            match source.read() {
+                Ok(s) => return Ok((source, s)),
+                Err(e) => tried.push((source, e)),
+            }
+        }
+
+        Err(InputError { day, tried })
+    }
+}
+
+/// The workspace this was built from
+fn workspace() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+/// Where a day's downloaded files belong: its folder in `AOC_INPUT_DIR` if that's set, or else in
+/// the workspace. Either way it's somewhere [InputResolver] looks.
+pub fn day_dir(day: u8) -> PathBuf {
+    let root = env::var_os(INPUT_DIR_VAR).map_or_else(workspace, PathBuf::from);
+    root.join(format!("day{day:02}"))
+}
+
+/// Read a day's input using the command line argument (if any) and the environment
+pub fn read_input(day: u8, explicit: Option<&str>) -> Result<String, InputError> {
+    InputResolver::new(explicit).read(day)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{fs, path::PathBuf};
+
+    use super::{InputResolver, InputSource};
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-input-{name}-{}", std::process::id()));
+        fs::create_dir_all(dir.join("day07")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn explicit_is_the_only_candidate() {
+        let resolver = InputResolver {
+            explicit: Some("-".into()),
+            input_dir: Some(PathBuf::from("/inputs")),
+            search_roots: vec![PathBuf::from(".")],
+        };
+        assert_eq!(resolver.candidates(7), vec![InputSource::Stdin]);
+    }
+
+    #[test]
+    fn input_dir_comes_before_convention() {
+        let resolver = InputResolver {
+            explicit: None,
+            input_dir: Some(PathBuf::from("/inputs")),
+            search_roots: vec![PathBuf::from(".")],
+        };
+        assert_eq!(
+            resolver.candidates(7),
+            vec![
+                InputSource::File(PathBuf::from("/inputs/day07/input.txt")),
+                InputSource::File(PathBuf::from("/inputs/day07.txt")),
+                InputSource::File(PathBuf::from("./day07/input.txt")),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_first_existing() {
+        let dir = scratch_dir("first");
+        fs::write(dir.join("day07.txt"), "flat").unwrap();
+
+        let resolver = InputResolver {
+            explicit: None,
+            input_dir: Some(dir.clone()),
+            search_roots: vec![],
+        };
+        assert_eq!(resolver.read(7).unwrap(), "flat");
+
+        fs::write(dir.join("day07").join("input.txt"), "nested").unwrap();
+        assert_eq!(resolver.read(7).unwrap(), "nested");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn error_names_every_path() {
+        let resolver = InputResolver {
+            explicit: None,
+            input_dir: Some(PathBuf::from("/nope")),
+            search_roots: vec![PathBuf::from("/also-nope")],
+        };
+        let err = resolver.read(3).unwrap_err();
+        assert_eq!(err.tried.len(), 3);
+
+        let message = err.to_string();
+        assert!(message.contains("/nope/day03/input.txt"));
+        assert!(message.contains("/nope/day03.txt"));
+        assert!(message.contains("/also-nope/day03/input.txt"));
+    }
+}
@@ -0,0 +1,371 @@
+use std::fmt::Debug;
+
+/// Whole numbers that intervals can be made of, knowing their neighbors so that `1..=3` and
+/// `4..=6` can be joined into `1..=6`
+pub trait Integer: Copy + Ord + Debug {
+    /// The next number up, or the same number if there isn't one
+    fn succ(self) -> Self;
+    /// The next number down, or the same number if there isn't one
+    fn pred(self) -> Self;
+    /// How many steps apart two numbers are
+    fn distance(self, other: Self) -> u64;
+}
+
+macro_rules! integer {
+    ($($t:ty),+) => {
+        $(impl Integer for $t {
+            fn succ(self) -> Self {
+                self.saturating_add(1)
+            }
+
+            fn pred(self) -> Self {
+                self.saturating_sub(1)
+            }
+
+            fn distance(self, other: Self) -> u64 {
+                self.abs_diff(other) as u64
+            }
+        })+
+    };
+}
+
+integer!(i32, i64, u32, u64, usize);
+
+/// Every whole number from `start` to `end`, both included, so it's never empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    start: T,
+    end: T,
+}
+
+impl<T: Integer> Interval<T> {
+    /// The interval from `start` to `end`, or None if `end` comes first
+    pub fn new(start: T, end: T) -> Option<Interval<T>> {
+        (start <= end).then_some(Interval { start, end })
+    }
+
+    /// An interval holding just `x`
+    pub fn single(x: T) -> Interval<T> {
+        Interval { start: x, end: x }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn end(&self) -> T {
+        self.end
+    }
+
+    /// How many numbers are in the interval
+    pub fn len(&self) -> u64 {
+        self.start.distance(self.end) + 1
+    }
+
+    /// Always false, since an interval holds at least its start
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.start <= x && x <= self.end
+    }
+
+    /// Whether every number in `other` is in this interval too
+    pub fn contains_interval(&self, other: &Interval<T>) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    /// Whether the intervals have any numbers in common
+    pub fn overlaps(&self, other: &Interval<T>) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    /// The numbers in both intervals, if there are any
+    pub fn intersection(&self, other: &Interval<T>) -> Option<Interval<T>> {
+        Interval::new(self.start.max(other.start), self.end.min(other.end))
+    }
+}
+
+/// Any collection of whole numbers, stored as the fewest intervals that cover them. The
+/// intervals are kept in order, and none of them overlap or even touch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { intervals: vec![] }
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet::default()
+    }
+
+    /// Add every number in `interval`, merging it with any intervals it touches
+    pub fn insert(&mut self, interval: Interval<T>) {
+        // everything before `first` ends too early to touch, and everything from `last` on starts
+        // too late
+        let first = self
+            .intervals
+            .partition_point(|i| i.end.succ() < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|i| i.start <= interval.end.succ());
+
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |acc, i| Interval {
+                start: acc.start.min(i.start),
+                end: acc.end.max(i.end),
+            });
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// The numbers in either set
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut result = self.clone();
+        result.extend(other.iter().copied());
+        result
+    }
+
+    /// The numbers in both sets
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = vec![];
+        let (mut a, mut b) = (self.intervals.iter(), other.intervals.iter());
+        let (mut x, mut y) = (a.next(), b.next());
+
+        while let (Some(i), Some(j)) = (x, y) {
+            intervals.extend(i.intersection(j));
+
+            // whichever ends first can't overlap anything else on the other side
+            if i.end < j.end {
+                x = a.next();
+            } else {
+                y = b.next();
+            }
+        }
+
+        // pieces of normalized intervals can't touch each other, so this is already normalized
+        IntervalSet { intervals }
+    }
+
+    /// The numbers in this set that aren't in `other`
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let mut intervals = vec![];
+        let mut others = other.intervals.iter().peekable();
+
+        for &interval in &self.intervals {
+            let mut remaining = Some(interval);
+            while let Some(rest) = remaining {
+                // skip everything that's over before this starts
+                while others.next_if(|o| o.end < rest.start).is_some() {}
+
+                match others.peek() {
+                    Some(o) if o.start <= rest.end => {
+                        if rest.start < o.start {
+                            intervals.push(Interval {
+                                start: rest.start,
+                                end: o.start.pred(),
+                            });
+                        }
+                        remaining = (o.end < rest.end).then(|| Interval {
+                            start: o.end.succ(),
+                            end: rest.end,
+                        });
+                    }
+                    _ => {
+                        intervals.push(rest);
+                        remaining = None;
+                    }
+                }
+            }
+        }
+
+        IntervalSet { intervals }
+    }
+
+    /// How many numbers are in the set
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        let idx = self.intervals.partition_point(|i| i.end < x);
+        self.intervals.get(idx).is_some_and(|i| i.contains(x))
+    }
+
+    /// The intervals making up the set, in order
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    /// The smallest interval covering the whole set, or None if it's empty
+    pub fn span(&self) -> Option<Interval<T>> {
+        let first = self.intervals.first()?;
+        let last = self.intervals.last()?;
+        Some(Interval {
+            start: first.start,
+            end: last.end,
+        })
+    }
+
+    /// The missing stretches between the intervals, in order
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.windows(2).map(|pair| Interval {
+            start: pair[0].end.succ(),
+            end: pair[1].start.pred(),
+        })
+    }
+}
+
+impl<T: Integer> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        IntervalSet {
+            intervals: vec![interval],
+        }
+    }
+}
+
+impl<T: Integer> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Integer> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::{Interval, IntervalSet};
+
+    fn interval(start: i32, end: i32) -> Interval<i32> {
+        Interval::new(start, end).unwrap()
+    }
+
+    fn set(intervals: &[(i32, i32)]) -> IntervalSet<i32> {
+        intervals.iter().map(|&(s, e)| interval(s, e)).collect()
+    }
+
+    /// Every number in the set, one at a time, to check against
+    fn points(set: &IntervalSet<i32>) -> BTreeSet<i32> {
+        set.iter().flat_map(|i| i.start()..=i.end()).collect()
+    }
+
+    fn is_normalized(set: &IntervalSet<i32>) -> bool {
+        set.intervals
+            .windows(2)
+            .all(|pair| pair[0].end() + 1 < pair[1].start())
+    }
+
+    fn arbitrary_set() -> impl Strategy<Value = IntervalSet<i32>> {
+        prop::collection::vec((-50..50, 0..10), 0..8).prop_map(|v| {
+            v.into_iter()
+                .map(|(start, len)| interval(start, start + len))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn intervals() {
+        let a = interval(2, 8);
+        assert_eq!(Interval::new(3, 2), None);
+        assert_eq!(a.len(), 7);
+        assert!(a.contains_interval(&interval(3, 7)));
+        assert!(!a.contains_interval(&interval(3, 9)));
+        assert!(a.overlaps(&interval(8, 9)));
+        assert!(!a.overlaps(&interval(9, 9)));
+        assert_eq!(a.intersection(&interval(6, 12)), Some(interval(6, 8)));
+    }
+
+    #[test]
+    fn merging() {
+        let s = set(&[(10, 12), (1, 3), (4, 5), (20, 25), (11, 15)]);
+        assert_eq!(s, set(&[(1, 5), (10, 15), (20, 25)]));
+        assert_eq!(s.len(), 17);
+        assert_eq!(s.span(), Some(interval(1, 25)));
+        assert_eq!(
+            s.gaps().collect::<Vec<_>>(),
+            vec![interval(6, 9), interval(16, 19)]
+        );
+        assert!(s.contains(11) && !s.contains(8));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25)]);
+        assert_eq!(a.union(&b), set(&[(0, 30)]));
+        assert_eq!(a.intersection(&b), set(&[(5, 10), (20, 25)]));
+        assert_eq!(a.difference(&b), set(&[(0, 4), (26, 30)]));
+        assert_eq!(b.difference(&a), set(&[(11, 19)]));
+    }
+
+    proptest! {
+        #[test]
+        fn insert_matches_points(s in arbitrary_set()) {
+            prop_assert!(is_normalized(&s));
+            prop_assert_eq!(s.len(), points(&s).len() as u64);
+        }
+
+        #[test]
+        fn operations_match_points(a in arbitrary_set(), b in arbitrary_set()) {
+            let (pa, pb) = (points(&a), points(&b));
+
+            let union = a.union(&b);
+            prop_assert!(is_normalized(&union));
+            prop_assert_eq!(points(&union), &pa | &pb);
+
+            let intersection = a.intersection(&b);
+            prop_assert!(is_normalized(&intersection));
+            prop_assert_eq!(points(&intersection), &pa & &pb);
+
+            let difference = a.difference(&b);
+            prop_assert!(is_normalized(&difference));
+            prop_assert_eq!(points(&difference), &pa - &pb);
+        }
+
+        #[test]
+        fn gaps_fill_the_span(s in arbitrary_set()) {
+            let gaps: IntervalSet<i32> = s.gaps().collect();
+            prop_assert!(points(&gaps).is_disjoint(&points(&s)));
+
+            let everything = s.union(&gaps);
+            prop_assert_eq!(everything.iter().count(), s.span().into_iter().count());
+            prop_assert_eq!(everything.span(), s.span());
+        }
+
+        #[test]
+        fn contains_matches_points(s in arbitrary_set(), x in -60..70) {
+            prop_assert_eq!(s.contains(x), points(&s).contains(&x));
+        }
+    }
+}
@@ -1,44 +1,23 @@
-use std::fmt::Debug;
-use std::str::FromStr;
-
-pub mod dijkstra;
+pub mod animate;
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod cycle;
+pub mod error;
+pub mod fetch;
+pub mod generate;
+pub mod input;
+pub mod interval;
 pub mod map;
 pub mod nom;
 pub mod orthogonal;
+pub mod point;
+pub mod render;
+pub mod report;
+pub mod search;
+pub mod solution;
+pub mod sparse;
 pub mod test;
 pub mod util;
 
-pub fn get_input_strings() -> Vec<String> {
-    get_input(|s| s.to_owned())
-}
-
-/**
- * Gets the current executable path so we can figure out where the input.txt and test.txt files are
- */
-fn get_current_path() -> String {
-    let e = std::env::current_exe().unwrap();
-    let p = e.file_name().unwrap().to_str().unwrap();
-    p.to_owned()
-}
-
-pub fn get_raw_input() -> String {
-    let path = format!("./{}/input.txt", get_current_path());
-    util::get_raw_input(&path)
-}
-
-pub fn get_input<T, F>(f: F) -> Vec<T>
-where
-    F: Fn(&str) -> T,
-{
-    let path = format!("./{}/input.txt", get_current_path());
-    util::get_input(&path, f)
-}
-
-pub fn get_numbers<T>() -> Vec<T>
-where
-    T: FromStr + Debug,
-    <T as FromStr>::Err: Debug,
-{
-    let path = format!("./{}/input.txt", get_current_path());
-    util::get_numbers(&path)
-}
+pub use solution::{run, solve, Answer, Part, Solution};
@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 
 // This is my Map from last year
+#[derive(PartialEq, Eq)]
 pub struct Map<T> {
     pub points: Vec<Vec<T>>,
     pub height: usize,
@@ -23,7 +24,7 @@ impl<T> Map<T> {
         self.points[y][x] = data;
     }
 
-    pub fn get(&self, (x, y): Coord) -> MapSquare<T> {
+    pub fn get(&self, (x, y): Coord) -> MapSquare<'_, T> {
         let data = &self.points[y][x];
         MapSquare {
             map: self,
@@ -32,44 +33,179 @@ impl<T> Map<T> {
         }
     }
 
-    pub fn neighbors(&self, (x, y): Coord) -> Vec<MapSquare<T>> {
-        let mut v = Vec::new();
-        if y != 0 {
-            v.push(self.get((x, y - 1)));
+    /// The squares up, left, down and right of `coords`, skipping any that are off the map
+    pub fn neighbors(&self, coords: Coord) -> Neighbors<'_, T> {
+        Neighbors {
+            map: self,
+            coords,
+            next: 0,
         }
+    }
+
+    /// Every square, row by row
+    pub fn iter(&self) -> Squares<'_, T> {
+        Squares { map: self, next: 0 }
+    }
+
+    /// Every cell with its coordinates, row by row, for updating in place
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Coord, &mut T)> {
+        self.points.iter_mut().enumerate().flat_map(|(y, row)| {
+            row.iter_mut()
+                .enumerate()
+                .map(move |(x, data)| ((x, y), data))
+        })
+    }
 
-        if x != 0 {
-            v.push(self.get((x - 1, y)));
+    /// The squares of row `y`, left to right
+    pub fn row(&self, y: usize) -> Line<'_, T> {
+        Line {
+            map: self,
+            next: (0, y),
+            step: (1, 0),
+            remaining: self.width,
         }
+    }
 
-        if y != self.height - 1 {
-            v.push(self.get((x, y + 1)));
+    /// The squares of column `x`, top to bottom
+    pub fn column(&self, x: usize) -> Line<'_, T> {
+        Line {
+            map: self,
+            next: (x, 0),
+            step: (0, 1),
+            remaining: self.height,
         }
+    }
 
-        if x != self.width - 1 {
-            v.push(self.get((x + 1, y)));
+    pub fn rows(&self) -> impl Iterator<Item = Line<'_, T>> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = Line<'_, T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// The squares in a straight line from `from` (not including it) out to the edge of the map,
+    /// moving by `step` each time
+    pub fn ray(&self, (x, y): Coord, (dx, dy): (isize, isize)) -> Line<'_, T> {
+        // how many steps fit along one axis before falling off the map
+        let fits = |pos: usize, delta: isize, len: usize| match delta {
+            0 => usize::MAX,
+            d if d > 0 => (len - 1 - pos) / d as usize,
+            d => pos / d.unsigned_abs(),
+        };
+
+        let remaining = match (dx, dy) {
+            (0, 0) => 0,
+            _ => fits(x, dx, self.width).min(fits(y, dy, self.height)),
+        };
+
+        Line {
+            map: self,
+            next: (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy)),
+            step: (dx, dy),
+            remaining,
         }
-        v
     }
 }
 
 impl<'a, T> IntoIterator for &'a Map<T> {
     type Item = MapSquare<'a, T>;
 
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = Squares<'a, T>;
 
     fn into_iter(self) -> Self::IntoIter {
-        (0..self.height)
-            .flat_map(|y| {
-                (0..self.width)
-                    .map(|x| self.get((x, y)))
-                    .collect::<Vec<MapSquare<'a, T>>>()
-            })
-            .collect::<Vec<MapSquare<'a, T>>>()
-            .into_iter()
+        self.iter()
+    }
+}
+
+/// Every square of a map, row by row. See [`Map::iter`].
+pub struct Squares<'a, T> {
+    map: &'a Map<T>,
+    next: usize,
+}
+
+impl<'a, T> Iterator for Squares<'a, T> {
+    type Item = MapSquare<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.map.width * self.map.height {
+            return None;
+        }
+
+        let coords = (self.next % self.map.width, self.next / self.map.width);
+        self.next += 1;
+        Some(self.map.get(coords))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.map.width * self.map.height - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Squares<'_, T> {}
+
+/// The neighbors of a square, in the order up, left, down, right. See [`Map::neighbors`].
+pub struct Neighbors<'a, T> {
+    map: &'a Map<T>,
+    coords: Coord,
+    next: usize,
+}
+
+impl<'a, T> Iterator for Neighbors<'a, T> {
+    type Item = MapSquare<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        const OFFSETS: [(isize, isize); 4] = [(0, -1), (-1, 0), (0, 1), (1, 0)];
+
+        let (x, y) = self.coords;
+        while let Some(&(dx, dy)) = OFFSETS.get(self.next) {
+            self.next += 1;
+
+            let neighbor = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+            // anything off the top or left wraps around to a huge number, so one check covers both
+            if neighbor.0 < self.map.width && neighbor.1 < self.map.height {
+                return Some(self.map.get(neighbor));
+            }
+        }
+
+        None
+    }
+}
+
+/// Squares in a straight line across a map: a row, a column or a ray
+pub struct Line<'a, T> {
+    map: &'a Map<T>,
+    next: Coord,
+    step: (isize, isize),
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Line<'a, T> {
+    type Item = MapSquare<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let square = self.map.get(self.next);
+        let (x, y) = self.next;
+        self.next = (
+            x.wrapping_add_signed(self.step.0),
+            y.wrapping_add_signed(self.step.1),
+        );
+        self.remaining -= 1;
+        Some(square)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
     }
 }
 
+impl<T> ExactSizeIterator for Line<'_, T> {}
+
 #[derive(Debug)]
 pub struct MapSquare<'a, T> {
     map: &'a Map<T>,
@@ -78,7 +214,7 @@ pub struct MapSquare<'a, T> {
 }
 
 impl<'a, T> MapSquare<'a, T> {
-    pub fn neighbors(&self) -> Vec<MapSquare<'a, T>> {
+    pub fn neighbors(&self) -> Neighbors<'a, T> {
         self.map.neighbors(self.coords)
     }
 
@@ -103,3 +239,62 @@ where
         writeln!(f)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::Map;
+
+    fn grid() -> Map<u32> {
+        Map::new(vec![vec![1, 2, 3], vec![4, 5, 6]])
+    }
+
+    fn data<'a>(squares: impl Iterator<Item = super::MapSquare<'a, u32>>) -> Vec<u32> {
+        squares.map(|s| *s.data).collect()
+    }
+
+    #[test]
+    fn iterates_row_by_row() {
+        let map = grid();
+        assert_eq!(data(map.iter()), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(map.iter().len(), 6);
+        assert_eq!(map.iter().nth(4).unwrap().coords, (1, 1));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let map = grid();
+        assert_eq!(data(map.row(1)), vec![4, 5, 6]);
+        assert_eq!(data(map.column(2)), vec![3, 6]);
+        assert_eq!(map.rows().map(data).collect::<Vec<_>>().len(), 2);
+        assert_eq!(
+            map.columns().map(data).collect::<Vec<_>>(),
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]]
+        );
+    }
+
+    #[test]
+    fn neighbors_stay_on_the_map() {
+        let map = grid();
+        assert_eq!(data(map.neighbors((0, 0))), vec![4, 2]);
+        assert_eq!(data(map.neighbors((1, 1))), vec![2, 4, 6]);
+        assert_eq!(data(map.get((2, 0)).neighbors()), vec![2, 6]);
+    }
+
+    #[test]
+    fn rays() {
+        let map = grid();
+        assert_eq!(data(map.ray((2, 1), (-1, 0))), vec![5, 4]);
+        assert_eq!(data(map.ray((0, 0), (1, 1))), vec![5]);
+        assert_eq!(data(map.ray((0, 0), (0, -1))), vec![]);
+        assert_eq!(data(map.ray((1, 0), (0, 0))), vec![]);
+    }
+
+    #[test]
+    fn iter_mut() {
+        let mut map = grid();
+        for ((x, y), data) in map.iter_mut() {
+            *data = (x * 10 + y) as u32;
+        }
+        assert_eq!(map.points, vec![vec![0, 10, 20], vec![1, 11, 21]]);
+    }
+}
@@ -1,12 +1,61 @@
 use nom::{
     bytes::complete::tag,
-    character::complete::{anychar, u32 as nom_u32},
-    combinator::{map, map_opt},
-    sequence::separated_pair,
-    IResult,
+    character::complete::{anychar, char, i64 as nom_i64, line_ending, space0, u32 as nom_u32},
+    combinator::{map, map_opt, value},
+    error::{ErrorKind, ParseError as _, VerboseError, VerboseErrorKind},
+    multi::separated_list1,
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
+    Err, Parser,
 };
 
-use crate::map::Coord;
+use crate::{
+    error::ParseError,
+    map::Coord,
+    point::{Point2, Point3},
+};
+
+/// nom's `IResult`, but defaulting to `VerboseError` so failures keep their `context` labels
+pub type IResult<I, O, E = VerboseError<I>> = nom::IResult<I, O, E>;
+
+/// Run `parser` over the whole input, turning any failure into a located `ParseError`. Anything
+/// left over other than trailing whitespace is an error too, instead of being silently dropped.
+pub fn parse_complete<'a, T, P>(input: &'a str, mut parser: P) -> Result<T, ParseError>
+where
+    P: Parser<&'a str, T, VerboseError<&'a str>>,
+{
+    match parser.parse(input) {
+        Ok((remaining, result)) => {
+            if remaining.trim_end().is_empty() {
+                Ok(result)
+            } else {
+                // skip past the separator the parser stopped in front of
+                let unparsed = remaining.trim_start_matches(['\r', '\n']);
+                Err(ParseError::at(input, unparsed, "expected end of input"))
+            }
+        }
+        Err(Err::Error(e) | Err::Failure(e)) => Err(ParseError::from_verbose(input, e)),
+        Err(Err::Incomplete(_)) => Err(ParseError::at(
+            input,
+            &input[input.len()..],
+            "unexpected end of input",
+        )),
+    }
+}
+
+/// Parse every line of the input with the same parser, each one required to be consumed in full,
+/// so errors point at the line that's actually broken
+pub fn parse_lines<'a, T, P>(input: &'a str, mut parser: P) -> Result<Vec<T>, ParseError>
+where
+    P: Parser<&'a str, T, VerboseError<&'a str>>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            parse_complete(line, |s| parser.parse(s)).map_err(|e| e.offset_lines(idx))
+        })
+        .collect()
+}
 
 pub fn single_digit(s: &str) -> IResult<&str, u32> {
     map_opt(anychar, |c| c.to_digit(10))(s)
@@ -17,3 +66,197 @@ pub fn coord(s: &str) -> IResult<&str, Coord> {
         (x as usize, y as usize)
     })(s)
 }
+
+/// A rectangle of characters, one row per line, with `cell` turning each character into a `T`.
+/// The grid runs until a blank line or the end of the input. Rows don't have to be the same
+/// length, and any character `cell` returns None for is a hard failure pointing right at it.
+pub fn grid<'a, T>(
+    cell: impl Fn(char) -> Option<T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<T>>> {
+    let row = move |s: &'a str| {
+        let end = s.find(['\r', '\n']).unwrap_or(s.len());
+        if end == 0 {
+            return Err(Err::Error(VerboseError::from_error_kind(
+                s,
+                ErrorKind::Many1,
+            )));
+        }
+
+        let mut cells = Vec::with_capacity(end);
+        for (offset, c) in s[..end].char_indices() {
+            match cell(c) {
+                Some(data) => cells.push(data),
+                None => {
+                    return Err(Err::Failure(VerboseError {
+                        errors: vec![(&s[offset..], VerboseErrorKind::Context("grid cell"))],
+                    }))
+                }
+            }
+        }
+
+        Ok((&s[end..], cells))
+    };
+
+    separated_list1(line_ending, row)
+}
+
+/// An empty line between two blocks of input, taking the line break before it too
+pub fn blank_line(s: &str) -> IResult<&str, ()> {
+    value((), pair(line_ending, line_ending))(s)
+}
+
+/// Blocks of input separated by blank lines, each parsed by `section`
+pub fn sections<'a, T>(
+    section: impl Parser<&'a str, T, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    separated_list1(blank_line, section)
+}
+
+/// A comma and any spaces after it
+fn comma(s: &str) -> IResult<&str, ()> {
+    value((), pair(char(','), space0))(s)
+}
+
+/// One or more `item`s separated by commas, with or without spaces after them, like `1,2,3` or
+/// `79, 98`
+pub fn comma_list<'a, T>(
+    item: impl Parser<&'a str, T, VerboseError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    separated_list1(comma, item)
+}
+
+/// Two signed numbers separated by a comma, like `-3,12`
+pub fn point2(s: &str) -> IResult<&str, Point2<i64>> {
+    map(separated_pair(nom_i64, comma, nom_i64), Point2::from)(s)
+}
+
+/// Two signed numbers labelled with their axes, like `x=-3, y=12`
+pub fn labelled_point2(s: &str) -> IResult<&str, Point2<i64>> {
+    map(
+        separated_pair(
+            preceded(tag("x="), nom_i64),
+            comma,
+            preceded(tag("y="), nom_i64),
+        ),
+        Point2::from,
+    )(s)
+}
+
+/// Three signed numbers separated by commas, like `1,-2,3`
+pub fn point3(s: &str) -> IResult<&str, Point3<i64>> {
+    map(
+        tuple((
+            terminated(nom_i64, comma),
+            terminated(nom_i64, comma),
+            nom_i64,
+        )),
+        Point3::from,
+    )(s)
+}
+
+#[cfg(test)]
+mod test {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{newline, u32 as nom_u32},
+        error::context,
+        multi::separated_list1,
+        sequence::preceded,
+    };
+
+    use crate::point::{Point2, Point3};
+
+    use super::{
+        comma_list, grid, labelled_point2, parse_complete, parse_lines, point2, point3, sections,
+        IResult,
+    };
+
+    fn item(s: &str) -> IResult<&str, u32> {
+        context("item", preceded(tag("item "), nom_u32))(s)
+    }
+
+    #[test]
+    fn complete() {
+        let result = parse_complete("item 1\nitem 2\n", separated_list1(newline, item));
+        assert_eq!(result, Ok(vec![1, 2]));
+    }
+
+    #[test]
+    fn leftovers_are_errors() {
+        let err =
+            parse_complete("item 1\nitem 2\nitme 3", separated_list1(newline, item)).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.found(), "itme 3");
+    }
+
+    #[test]
+    fn failures_have_context() {
+        let err = parse_lines("item 1\nitem x", item).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.text, "item x");
+        assert_eq!(err.found(), "x");
+        assert_eq!(err.context, vec!["item"]);
+        assert_eq!(
+            err.to_string(),
+            "parse error at line 2, column 6 (in item): digit parser failed but found \"x\"\n2 | item x\n  |      ^"
+        );
+    }
+
+    #[test]
+    fn grids() {
+        let digit = |c: char| c.to_digit(10);
+        assert_eq!(
+            parse_complete("123\n45\n", grid(digit)),
+            Ok(vec![vec![1, 2, 3], vec![4, 5]])
+        );
+
+        // the grid stops at a blank line
+        let result = grid(digit)("12\r\n34\n\nrest");
+        assert_eq!(result, Ok(("\n\nrest", vec![vec![1, 2], vec![3, 4]])));
+
+        let err = parse_complete("123\n4x6", grid(digit)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.message, "couldn't parse grid cell");
+    }
+
+    #[test]
+    fn blank_line_sections() {
+        let numbers = separated_list1(newline, nom_u32);
+        assert_eq!(
+            parse_complete("1\n2\n\n3\n\n4\n5\n", sections(numbers)),
+            Ok(vec![vec![1, 2], vec![3], vec![4, 5]])
+        );
+
+        // two blank lines in a row isn't a separator, so the rest is left over
+        let err = parse_complete("1\n\n\n2", sections(nom_u32)).unwrap_err();
+        assert_eq!(err.line, 4);
+    }
+
+    #[test]
+    fn points() {
+        assert_eq!(parse_complete("-3,12", point2), Ok(Point2::new(-3, 12)));
+        assert_eq!(
+            parse_complete("x=-3, y=+12", labelled_point2),
+            Ok(Point2::new(-3, 12))
+        );
+        assert_eq!(parse_complete("1,-2, 3", point3), Ok(Point3::new(1, -2, 3)));
+        assert!(parse_complete("1,2", point3).is_err());
+        assert!(parse_complete("y=1, x=2", labelled_point2).is_err());
+    }
+
+    #[test]
+    fn comma_lists() {
+        assert_eq!(
+            parse_complete("1,2,3", comma_list(nom_u32)),
+            Ok(vec![1, 2, 3])
+        );
+        assert_eq!(
+            parse_complete("79, 98", comma_list(nom_u32)),
+            Ok(vec![79, 98])
+        );
+        assert_eq!(parse_complete("5", comma_list(nom_u32)), Ok(vec![5]));
+
+        let err = parse_complete("1,2,", comma_list(nom_u32)).unwrap_err();
+        assert_eq!(err.column, 4);
+    }
+}
@@ -1,11 +1,11 @@
-use crate::map::{Map, MapSquare};
+use crate::map::{Line, Map, MapSquare};
 
-#[derive(Debug)]
+/// Everything in a straight line from a square to each edge of the map, nearest first
 pub struct OrthogonalNeighbors<'a, T> {
-    pub north: Vec<MapSquare<'a, T>>,
-    pub south: Vec<MapSquare<'a, T>>,
-    pub east: Vec<MapSquare<'a, T>>,
-    pub west: Vec<MapSquare<'a, T>>,
+    pub north: Line<'a, T>,
+    pub south: Line<'a, T>,
+    pub east: Line<'a, T>,
+    pub west: Line<'a, T>,
 }
 
 pub trait Orthogonal<'a, T> {
@@ -14,19 +14,12 @@ pub trait Orthogonal<'a, T> {
 
 impl<'a, T> Orthogonal<'a, T> for Map<T> {
     fn orthogonal_neighbors(&'a self, square: &MapSquare<T>) -> OrthogonalNeighbors<'a, T> {
-        let (x, y) = square.coords;
-
         // check the vertical and horizontal from this square
-        let north = (0..y).rev().map(|dy| self.get((x, dy))).collect();
-        let south = (y + 1..self.height).map(|dy| self.get((x, dy))).collect();
-        let west = (0..x).rev().map(|dx| self.get((dx, y))).collect();
-        let east = (x + 1..self.width).map(|dx| self.get((dx, y))).collect();
-
         OrthogonalNeighbors {
-            north,
-            south,
-            east,
-            west,
+            north: self.ray(square.coords, (0, -1)),
+            south: self.ray(square.coords, (0, 1)),
+            east: self.ray(square.coords, (1, 0)),
+            west: self.ray(square.coords, (-1, 0)),
         }
     }
 }
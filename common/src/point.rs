@@ -0,0 +1,425 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position or offset on a plane. Like the rows of a puzzle input, `y` grows downwards, so
+/// `Dir4::Up` takes one off `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A position or offset in space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// How far apart two numbers are, without going negative for unsigned types
+fn distance<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+
+    /// Apply `f` to both coordinates, usually to change their type
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Point2<U> {
+        Point2::new(f(self.x), f(self.y))
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point2<T> {
+    /// The number of steps between the points moving only up, down, left or right
+    pub fn manhattan(self, other: Point2<T>) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    /// The number of steps between the points when diagonal steps are allowed too
+    pub fn chebyshev(self, other: Point2<T>) -> T {
+        distance(self.x, other.x).max(distance(self.y, other.y))
+    }
+}
+
+impl<T: Neg<Output = T>> Point2<T> {
+    /// Turn a quarter turn anticlockwise around the origin, as it looks on screen
+    pub fn rotate_left(self) -> Point2<T> {
+        Point2::new(self.y, -self.x)
+    }
+
+    /// Turn a quarter turn clockwise around the origin, as it looks on screen
+    pub fn rotate_right(self) -> Point2<T> {
+        Point2::new(-self.y, self.x)
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+
+    /// Apply `f` to every coordinate, usually to change their type
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Point3<U> {
+        Point3::new(f(self.x), f(self.y), f(self.z))
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    /// The number of steps between the points moving along one axis at a time
+    pub fn manhattan(self, other: Point3<T>) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y) + distance(self.z, other.z)
+    }
+
+    /// The number of steps between the points when diagonal steps are allowed too
+    pub fn chebyshev(self, other: Point3<T>) -> T {
+        distance(self.x, other.x)
+            .max(distance(self.y, other.y))
+            .max(distance(self.z, other.z))
+    }
+}
+
+impl<T: Copy + Add<Output = T> + Sub<Output = T> + From<u8>> Point3<T> {
+    /// The six points sharing a face with this one. With unsigned coordinates, none of them can be
+    /// zero.
+    pub fn adjacent(self) -> [Point3<T>; 6] {
+        let one = T::from(1);
+        let Point3 { x, y, z } = self;
+        [
+            Point3::new(x + one, y, z),
+            Point3::new(x - one, y, z),
+            Point3::new(x, y + one, z),
+            Point3::new(x, y - one, z),
+            Point3::new(x, y, z + one),
+            Point3::new(x, y, z - one),
+        ]
+    }
+}
+
+/// The arithmetic operators, a coordinate at a time, along with scaling by a single number
+macro_rules! point_ops {
+    ($point:ident { $($axis:ident),+ }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = $point<T>;
+
+            fn add(self, other: $point<T>) -> $point<T> {
+                $point { $($axis: self.$axis + other.$axis),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = $point<T>;
+
+            fn sub(self, other: $point<T>) -> $point<T> {
+                $point { $($axis: self.$axis - other.$axis),+ }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = $point<T>;
+
+            fn mul(self, scale: T) -> $point<T> {
+                $point { $($axis: self.$axis * scale),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = $point<T>;
+
+            fn neg(self) -> $point<T> {
+                $point { $($axis: -self.$axis),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: $point<T>) {
+                $(self.$axis += other.$axis;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: $point<T>) {
+                $(self.$axis -= other.$axis;)+
+            }
+        }
+    };
+}
+
+point_ops!(Point2 { x, y });
+point_ops!(Point3 { x, y, z });
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(p: Point2<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(p: Point3<T>) -> Self {
+        (p.x, p.y, p.z)
+    }
+}
+
+impl<T> From<Point3<T>> for [T; 3] {
+    fn from(p: Point3<T>) -> Self {
+        [p.x, p.y, p.z]
+    }
+}
+
+/// One of the four directions along the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// Every direction, clockwise from up
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    /// The direction a quarter turn anticlockwise from this one
+    pub fn rotate_left(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 3) % 4]
+    }
+
+    /// The direction a quarter turn clockwise from this one
+    pub fn rotate_right(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn reverse(self) -> Dir4 {
+        Dir4::ALL[(self as usize + 2) % 4]
+    }
+
+    /// One step in this direction
+    pub fn delta<T: From<i8>>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Dir4::Up => (0, -1),
+            Dir4::Right => (1, 0),
+            Dir4::Down => (0, 1),
+            Dir4::Left => (-1, 0),
+        };
+        Point2::new(T::from(x), T::from(y))
+    }
+
+    /// `U`, `R`, `D` or `L`
+    pub fn from_letter(c: char) -> Option<Dir4> {
+        match c {
+            'U' => Some(Dir4::Up),
+            'R' => Some(Dir4::Right),
+            'D' => Some(Dir4::Down),
+            'L' => Some(Dir4::Left),
+            _ => None,
+        }
+    }
+
+    pub fn letter(self) -> char {
+        match self {
+            Dir4::Up => 'U',
+            Dir4::Right => 'R',
+            Dir4::Down => 'D',
+            Dir4::Left => 'L',
+        }
+    }
+
+    /// `^`, `>`, `v` or `<`
+    pub fn from_arrow(c: char) -> Option<Dir4> {
+        match c {
+            '^' => Some(Dir4::Up),
+            '>' => Some(Dir4::Right),
+            'v' => Some(Dir4::Down),
+            '<' => Some(Dir4::Left),
+            _ => None,
+        }
+    }
+
+    pub fn arrow(self) -> char {
+        match self {
+            Dir4::Up => '^',
+            Dir4::Right => '>',
+            Dir4::Down => 'v',
+            Dir4::Left => '<',
+        }
+    }
+}
+
+/// One of the eight directions along the grid or diagonally across it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    /// Every direction, clockwise from up
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    /// The direction an eighth of a turn anticlockwise from this one
+    pub fn rotate_left(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 7) % 8]
+    }
+
+    /// The direction an eighth of a turn clockwise from this one
+    pub fn rotate_right(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn reverse(self) -> Dir8 {
+        Dir8::ALL[(self as usize + 4) % 8]
+    }
+
+    /// One step in this direction, which is diagonal for half of them
+    pub fn delta<T: From<i8>>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Dir8::Up => (0, -1),
+            Dir8::UpRight => (1, -1),
+            Dir8::Right => (1, 0),
+            Dir8::DownRight => (1, 1),
+            Dir8::Down => (0, 1),
+            Dir8::DownLeft => (-1, 1),
+            Dir8::Left => (-1, 0),
+            Dir8::UpLeft => (-1, -1),
+        };
+        Point2::new(T::from(x), T::from(y))
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Dir8::Up,
+            Dir4::Right => Dir8::Right,
+            Dir4::Down => Dir8::Down,
+            Dir4::Left => Dir8::Left,
+        }
+    }
+}
+
+impl<T: From<i8> + Add<Output = T>> Add<Dir4> for Point2<T> {
+    type Output = Point2<T>;
+
+    fn add(self, dir: Dir4) -> Point2<T> {
+        self + dir.delta()
+    }
+}
+
+impl<T: From<i8> + Add<Output = T>> Add<Dir8> for Point2<T> {
+    type Output = Point2<T>;
+
+    fn add(self, dir: Dir8) -> Point2<T> {
+        self + dir.delta()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Dir4, Dir8, Point2, Point3};
+
+    #[test]
+    fn arithmetic() {
+        let a = Point2::new(3, -2);
+        let b = Point2::new(-1, 5);
+        assert_eq!(a + b, Point2::new(2, 3));
+        assert_eq!(a - b, Point2::new(4, -7));
+        assert_eq!(a * 3, Point2::new(9, -6));
+        assert_eq!(-a, Point2::new(-3, 2));
+
+        let mut c = a;
+        c += b;
+        c -= Point2::new(2, 2);
+        assert_eq!(c, Point2::new(0, 1));
+
+        let p = Point3::new(1, 2, 3);
+        assert_eq!(p + p * 2 - Point3::new(3, 3, 3), Point3::new(0, 3, 6));
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(1, 1);
+        let b = Point2::new(4, -3);
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (7, 4));
+
+        // unsigned coordinates mustn't underflow in either direction
+        let c = Point2::new(5usize, 0);
+        let d = Point2::new(2usize, 7);
+        assert_eq!((c.manhattan(d), d.manhattan(c)), (10, 10));
+
+        let e = Point3::new(1u32, 2, 3);
+        let f = Point3::new(3u32, 2, 0);
+        assert_eq!((e.manhattan(f), e.chebyshev(f)), (5, 3));
+    }
+
+    #[test]
+    fn rotation() {
+        let up: Point2<i64> = Dir4::Up.delta();
+        assert_eq!(up.rotate_right(), Dir4::Right.delta());
+        assert_eq!(up.rotate_left(), Dir4::Left.delta());
+
+        for dir in Dir4::ALL {
+            assert_eq!(dir.rotate_left().rotate_right(), dir);
+            assert_eq!(dir.rotate_right().rotate_right(), dir.reverse());
+            assert_eq!(
+                dir.delta::<i64>().rotate_right(),
+                dir.rotate_right().delta()
+            );
+        }
+
+        assert_eq!(Dir8::Up.rotate_left(), Dir8::UpLeft);
+        assert_eq!(Dir8::UpLeft.rotate_right(), Dir8::Up);
+        for dir in Dir8::ALL {
+            let Point2 { x, y } = dir.delta::<i64>() + dir.reverse().delta();
+            assert_eq!((x, y), (0, 0));
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(Point2::from((1, 2)), Point2::new(1, 2));
+        assert_eq!(<(i32, i32)>::from(Point2::new(1, 2)), (1, 2));
+        assert_eq!(Point3::from((1, 2, 3)), Point3::new(1, 2, 3));
+        assert_eq!(<[u8; 3]>::from(Point3::new(1, 2, 3)), [1, 2, 3]);
+        assert_eq!(Point2::new(1u8, 2).map(i64::from), Point2::new(1i64, 2));
+
+        assert_eq!(Point2::new(0i64, 0) + Dir4::Left, Point2::new(-1, 0));
+        assert_eq!(Point2::new(0i64, 0) + Dir8::DownRight, Point2::new(1, 1));
+        assert_eq!(Dir8::from(Dir4::Down), Dir8::Down);
+        assert_eq!(Dir4::from_letter('R'), Some(Dir4::Right));
+        assert_eq!(Dir4::from_letter('D').map(Dir4::letter), Some('D'));
+        assert_eq!(Dir4::from_arrow('v').map(Dir4::arrow), Some('v'));
+    }
+}
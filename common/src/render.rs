@@ -0,0 +1,426 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    map::Map,
+    sparse::{Bounds, Point, SparseGrid},
+};
+
+/// A colour, as red, green and blue
+pub type Rgb = [u8; 3];
+
+/// A picture of a grid at one point in time, one pixel per cell until it's scaled up
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Frame {
+    /// A frame filled in by calling `colour` for every pixel, row by row
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut colour: impl FnMut(usize, usize) -> Rgb,
+    ) -> Frame {
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| colour(x, y))
+            .collect();
+
+        Frame {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Blow every pixel up into a `factor` by `factor` square
+    pub fn scale(&self, factor: usize) -> Frame {
+        let factor = factor.max(1);
+        Frame::from_fn(self.width * factor, self.height * factor, |x, y| {
+            self.pixels[(y / factor) * self.width + x / factor]
+        })
+    }
+
+    /// The pixels as one long run of bytes, three to a pixel
+    fn bytes(&self) -> Vec<u8> {
+        self.pixels.iter().flatten().copied().collect()
+    }
+}
+
+/// Anything laid out on a grid that can be drawn a cell at a time. The palette picks the colour
+/// for each cell, so the same state can be drawn in different ways.
+pub trait Render {
+    type Cell<'a>
+    where
+        Self: 'a;
+
+    /// How many cells wide and high the picture is
+    fn size(&self) -> (usize, usize);
+
+    /// The cell at `x` and `y` of the picture, counting from the top left
+    fn cell(&self, x: usize, y: usize) -> Self::Cell<'_>;
+
+    fn frame<'s>(&'s self, palette: impl Fn(Self::Cell<'s>) -> Rgb) -> Frame {
+        let (width, height) = self.size();
+        Frame::from_fn(width, height, |x, y| palette(self.cell(x, y)))
+    }
+}
+
+impl<T> Render for Map<T> {
+    type Cell<'a>
+        = &'a T
+    where
+        T: 'a;
+
+    fn size(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> &T {
+        &self.points[y][x]
+    }
+}
+
+/// A window onto a `SparseGrid`, so every frame of an animation can be drawn the same size no
+/// matter how the grid grows
+pub struct Within<'g, T> {
+    grid: &'g SparseGrid<T>,
+    bounds: Bounds,
+}
+
+impl<T> SparseGrid<T> {
+    /// Draw just the cells inside `bounds`
+    pub fn within(&self, bounds: Bounds) -> Within<'_, T> {
+        Within { grid: self, bounds }
+    }
+}
+
+impl<T> Render for Within<'_, T> {
+    type Cell<'a>
+        = (Point, Option<&'a T>)
+    where
+        Self: 'a;
+
+    fn size(&self) -> (usize, usize) {
+        (self.bounds.width() as usize, self.bounds.height() as usize)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Self::Cell<'_> {
+        let p = Point::new(self.bounds.min_x + x as i64, self.bounds.min_y + y as i64);
+        (p, self.grid.get(p))
+    }
+}
+
+/// Enough snapshots for a smooth animation without the files getting huge
+pub const FRAME_LIMIT: usize = 150;
+
+/// Keeps snapshots of a simulation of any length without keeping all of them. Once there are
+/// twice as many as wanted, every other one is thrown away and only half as many steps get
+/// recorded from then on.
+#[derive(Debug, Clone)]
+pub struct Recorder<T> {
+    snapshots: Vec<T>,
+    limit: usize,
+    every: usize,
+    step: usize,
+}
+
+impl<T> Default for Recorder<T> {
+    fn default() -> Self {
+        Recorder::new(FRAME_LIMIT)
+    }
+}
+
+impl<T> Recorder<T> {
+    /// A recorder that ends up with between `limit` and twice `limit` snapshots, as long as the
+    /// simulation runs for that long
+    pub fn new(limit: usize) -> Recorder<T> {
+        Recorder {
+            snapshots: vec![],
+            limit: limit.max(1),
+            every: 1,
+            step: 0,
+        }
+    }
+
+    /// Call once per step of the simulation. `snapshot` only gets called for the steps that are
+    /// kept.
+    pub fn step(&mut self, snapshot: impl FnOnce() -> T) {
+        if self.step.is_multiple_of(self.every) {
+            self.snapshots.push(snapshot());
+
+            if self.snapshots.len() >= 2 * self.limit {
+                let mut idx = 0;
+                self.snapshots.retain(|_| {
+                    idx += 1;
+                    idx % 2 == 1
+                });
+                self.every *= 2;
+            }
+        }
+        self.step += 1;
+    }
+
+    /// Every snapshot that was kept, and the final state of the simulation after them
+    pub fn finish(mut self, last: T) -> Vec<T> {
+        self.snapshots.push(last);
+        self.snapshots
+    }
+}
+
+/// The file formats frames can be saved as, picked by the file's extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Gif,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> io::Result<Format> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("ppm") => Ok(Format::Ppm),
+            Some("png") => Ok(Format::Png),
+            Some("gif") => Ok(Format::Gif),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{} isn't a .ppm, .png or .gif file", path.display()),
+            )),
+        }
+    }
+}
+
+/// How long each frame of a GIF is shown for, in hundredths of a second
+const GIF_DELAY: u16 = 5;
+
+/// Save frames to `path`. A GIF gets every frame as an animation. PPM and PNG files only hold one
+/// picture, so a sequence of them is numbered, as `out-0001.png`, `out-0002.png` and so on.
+/// Returns the files that were written.
+pub fn save(frames: &[Frame], path: &Path) -> io::Result<Vec<PathBuf>> {
+    let format = Format::from_path(path)?;
+    if frames.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "there aren't any frames to save",
+        ));
+    }
+
+    if format == Format::Gif {
+        write_gif(frames, BufWriter::new(File::create(path)?))?;
+        return Ok(vec![path.to_owned()]);
+    }
+
+    let paths = if frames.len() == 1 {
+        vec![path.to_owned()]
+    } else {
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        let extension = path.extension().unwrap_or_default().to_string_lossy();
+        (1..=frames.len())
+            .map(|n| path.with_file_name(format!("{stem}-{n:04}.{extension}")))
+            .collect()
+    };
+
+    for (frame, path) in frames.iter().zip(&paths) {
+        let file = BufWriter::new(File::create(path)?);
+        match format {
+            Format::Ppm => write_ppm(frame, file)?,
+            Format::Png => write_png(frame, file)?,
+            Format::Gif => unreachable!(),
+        }
+    }
+
+    Ok(paths)
+}
+
+/// The binary flavour of PPM, which is just a tiny header and then the pixels
+pub fn write_ppm(frame: &Frame, mut out: impl Write) -> io::Result<()> {
+    write!(out, "P6\n{} {}\n255\n", frame.width, frame.height)?;
+    out.write_all(&frame.bytes())?;
+    out.flush()
+}
+
+pub fn write_png(frame: &Frame, out: impl Write) -> io::Result<()> {
+    let mut encoder = png::Encoder::new(out, frame.width as u32, frame.height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer
+        .write_image_data(&frame.bytes())
+        .map_err(io::Error::other)?;
+    writer.finish().map_err(io::Error::other)
+}
+
+/// An animated GIF that loops forever. Every frame has to be the same size, and there can't be
+/// more than 256 colours between all of them, which is plenty for a palette of cell colours.
+pub fn write_gif(frames: &[Frame], out: impl Write) -> io::Result<()> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+
+    let first = frames
+        .first()
+        .ok_or_else(|| invalid("there aren't any frames to save".to_owned()))?;
+    let (width, height) = (first.width, first.height);
+    if let Some(frame) = frames
+        .iter()
+        .find(|f| (f.width, f.height) != (width, height))
+    {
+        return Err(invalid(format!(
+            "every frame of a GIF has to be {width}x{height}, not {}x{}",
+            frame.width, frame.height
+        )));
+    }
+    let too_big =
+        |n: usize| u16::try_from(n).map_err(|_| invalid(format!("{n} is too big for a GIF")));
+    let (gif_width, gif_height) = (too_big(width)?, too_big(height)?);
+
+    // one palette shared by every frame
+    let mut palette: Vec<Rgb> = vec![];
+    let mut indices: HashMap<Rgb, u8> = HashMap::new();
+    let mut indexed = Vec::with_capacity(frames.len());
+    for frame in frames {
+        let mut pixels = Vec::with_capacity(frame.pixels.len());
+        // neighbouring pixels are nearly always the same colour, so skip the lookup for those
+        let mut last = None;
+        for &rgb in &frame.pixels {
+            let idx = match last.filter(|&(last_rgb, _)| last_rgb == rgb) {
+                Some((_, idx)) => idx,
+                None => match indices.get(&rgb) {
+                    Some(&idx) => idx,
+                    None => {
+                        let idx = u8::try_from(palette.len()).map_err(|_| {
+                            invalid("a GIF can't have more than 256 colours".to_owned())
+                        })?;
+                        palette.push(rgb);
+                        indices.insert(rgb, idx);
+                        idx
+                    }
+                },
+            };
+            last = Some((rgb, idx));
+            pixels.push(idx);
+        }
+        indexed.push(pixels);
+    }
+
+    // the palette has to be a power of two long
+    let padded = palette.len().next_power_of_two().max(2);
+    palette.resize(padded, [0, 0, 0]);
+
+    let mut encoder = gif::Encoder::new(out, gif_width, gif_height, palette.as_flattened())
+        .map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    for pixels in indexed {
+        let frame = gif::Frame {
+            width: gif_width,
+            height: gif_height,
+            delay: GIF_DELAY,
+            buffer: Cow::Owned(pixels),
+            ..Default::default()
+        };
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        map::Map,
+        sparse::{Bounds, Point, SparseGrid},
+    };
+
+    use super::{write_gif, write_ppm, Frame, Recorder, Render};
+
+    const BLACK: [u8; 3] = [0, 0, 0];
+    const WHITE: [u8; 3] = [255, 255, 255];
+
+    #[test]
+    fn render_and_scale() {
+        let map = Map::new(vec![vec![true, false], vec![false, true]]);
+        let frame = map.frame(|&on| if on { WHITE } else { BLACK });
+        assert_eq!((frame.width(), frame.height()), (2, 2));
+        assert_eq!(frame.get(1, 0), Some(BLACK));
+        assert_eq!(frame.get(1, 1), Some(WHITE));
+        assert_eq!(frame.get(2, 0), None);
+
+        let big = frame.scale(3);
+        assert_eq!((big.width(), big.height()), (6, 6));
+        assert_eq!(big.get(2, 2), Some(WHITE));
+        assert_eq!(big.get(3, 2), Some(BLACK));
+    }
+
+    #[test]
+    fn sparse_window() {
+        let grid: SparseGrid<()> = [(Point::new(-1, -1), ())].into_iter().collect();
+        let bounds = Bounds::around([Point::new(-2, -2), Point::new(1, 0)]).unwrap();
+
+        let frame = grid
+            .within(bounds)
+            .frame(|(_, cell)| if cell.is_some() { WHITE } else { BLACK });
+        assert_eq!((frame.width(), frame.height()), (4, 3));
+        assert_eq!(frame.get(1, 1), Some(WHITE));
+        assert_eq!(frame.get(0, 0), Some(BLACK));
+    }
+
+    #[test]
+    fn ppm() {
+        let frame = Frame::from_fn(2, 1, |x, _| if x == 0 { WHITE } else { BLACK });
+        let mut out = vec![];
+        write_ppm(&frame, &mut out).unwrap();
+        assert_eq!(out, b"P6\n2 1\n255\n\xff\xff\xff\x00\x00\x00");
+    }
+
+    #[test]
+    fn gif_frames_must_match() {
+        let small = Frame::from_fn(1, 1, |_, _| BLACK);
+        let big = Frame::from_fn(2, 2, |_, _| WHITE);
+
+        let mut out = vec![];
+        write_gif(&[small.clone(), small.clone()], &mut out).unwrap();
+        assert!(out.starts_with(b"GIF89a"));
+        assert!(write_gif(&[small, big], &mut vec![]).is_err());
+    }
+
+    #[test]
+    fn recorder_thins_out() {
+        let mut recorder = Recorder::new(4);
+        for step in 0..100 {
+            recorder.step(|| step);
+        }
+        let kept = recorder.finish(100);
+
+        assert!((4..=9).contains(&kept.len()));
+        assert_eq!(kept.first(), Some(&0));
+        assert_eq!(kept.last(), Some(&100));
+        // what's left is still evenly spread
+        let gaps: Vec<_> = kept[..kept.len() - 1]
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .collect();
+        assert!(gaps.windows(2).all(|w| w[0] == w[1]));
+    }
+}
@@ -0,0 +1,263 @@
+use std::{
+    fmt::{Display, Write},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{answers::Check, Answer, Part};
+
+/// How answers are printed: for people to read, or a record per day and part for other programs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    /// A JSON object per line
+    Json,
+    /// A header line, then a row per record
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("{s} is not a format, try text, json or csv")),
+        }
+    }
+}
+
+impl Format {
+    /// What has to be printed before any records
+    pub fn header(&self) -> Option<&'static str> {
+        match self {
+            Format::Csv => Some(CSV_HEADER),
+            Format::Text | Format::Json => None,
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+            Format::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+/// Everything there is to know about one part's answer from one run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Answer,
+    pub check: Check,
+    /// How long the part took, not counting parsing
+    pub elapsed: Duration,
+    /// The SHA-256 of the input, from [input_hash]
+    pub input_hash: String,
+    /// When the day was run
+    pub timestamp: SystemTime,
+}
+
+/// The first line of CSV output, naming the columns of [Record::to_csv]
+pub const CSV_HEADER: &str = "day,part,answer,type,check,elapsed_ms,input_hash,timestamp";
+
+impl Record {
+    fn check(&self) -> &'static str {
+        match self.check {
+            Check::Pass => "pass",
+            Check::Fail { .. } => "fail",
+            Check::Unknown => "unknown",
+        }
+    }
+
+    fn elapsed_ms(&self) -> String {
+        format!("{:.3}", self.elapsed.as_secs_f64() * 1000.0)
+    }
+
+    /// The record as a JSON object on one line. Answers are always strings, since some of them
+    /// are too big for a double or aren't numbers at all.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"type\":{},\"check\":\"{}\",\"elapsed_ms\":{},\"input_hash\":\"{}\",\"timestamp\":\"{}\"}}",
+            self.day,
+            self.part,
+            json_string(&self.answer.text),
+            json_string(self.answer.type_name),
+            self.check(),
+            self.elapsed_ms(),
+            self.input_hash,
+            rfc3339(self.timestamp),
+        )
+    }
+
+    /// The record as a row of CSV, in the order of [CSV_HEADER]
+    pub fn to_csv(&self) -> String {
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            csv_field(&self.answer.text),
+            csv_field(self.answer.type_name),
+            self.check().to_owned(),
+            self.elapsed_ms(),
+            self.input_hash.clone(),
+            rfc3339(self.timestamp),
+        ]
+        .join(",")
+    }
+}
+
+/// The SHA-256 of a puzzle input in hex, for telling which input a run used
+pub fn input_hash(raw: &str) -> String {
+    Sha256::digest(raw.as_bytes())
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Quoted only when it has to be, like day 10's answer which spans several lines
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+/// A UTC timestamp like `2022-12-25T05:00:00.000Z`
+fn rfc3339(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, secs_of_day) = ((secs / 86_400) as i64, secs % 86_400);
+
+    // days since 1970-01-01 to a calendar date, counting in 400 year eras that start in March
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        secs_of_day / 3_600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+        since_epoch.subsec_millis(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{csv_field, input_hash, json_string, rfc3339, Format, Record, CSV_HEADER};
+    use crate::{answers::Check, Answer, Part};
+
+    fn record(text: &str) -> Record {
+        Record {
+            day: 10,
+            part: Part::Two,
+            answer: Answer {
+                text: text.to_owned(),
+                type_name: "String",
+            },
+            check: Check::Pass,
+            elapsed: Duration::from_micros(1_500),
+            input_hash: input_hash(""),
+            timestamp: UNIX_EPOCH + Duration::from_millis(1_671_944_400_250),
+        }
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!(Format::Csv.to_string(), "csv");
+        assert!("xml".parse::<Format>().is_err());
+        assert_eq!(Format::Csv.header(), Some(CSV_HEADER));
+        assert_eq!(Format::Json.header(), None);
+    }
+
+    #[test]
+    fn hash() {
+        assert_eq!(
+            input_hash(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            rfc3339(UNIX_EPOCH + Duration::from_millis(1_671_944_400_250)),
+            "2022-12-25T05:00:00.250Z"
+        );
+        // a leap day
+        assert_eq!(
+            rfc3339(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            "2000-02-29T00:00:00.000Z"
+        );
+    }
+
+    #[test]
+    fn escaping() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), r#""a\"b\\c\nd\u0001""#);
+        assert_eq!(csv_field("1234"), "1234");
+        assert_eq!(csv_field("a,\"b\"\nc"), "\"a,\"\"b\"\"\nc\"");
+    }
+
+    #[test]
+    fn records() {
+        let hash = input_hash("");
+        assert_eq!(
+            record(".#\n#.").to_json(),
+            format!(
+                r#"{{"day":10,"part":2,"answer":".#\n#.","type":"String","check":"pass","elapsed_ms":1.500,"input_hash":"{hash}","timestamp":"2022-12-25T05:00:00.250Z"}}"#
+            )
+        );
+        assert_eq!(
+            record("42").to_csv(),
+            format!("10,2,42,String,pass,1.500,{hash},2022-12-25T05:00:00.250Z")
+        );
+        assert_eq!(CSV_HEADER.split(',').count(), 8);
+    }
+}
@@ -0,0 +1,375 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Anything that can be used as the cost of an edge. `Default` has to be zero.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// The goal a search reached, what it cost to get there, and enough bookkeeping to work out how
+#[derive(Debug, Clone)]
+pub struct Found<N, C> {
+    pub goal: N,
+    pub cost: C,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C> Found<N, C> {
+    /// Every node from the start the goal was reached from to the goal itself, both included
+    pub fn path(&self) -> Vec<N> {
+        let mut path = vec![self.goal.clone()];
+        while let Some(parent) = path.last().and_then(|n| self.parents.get(n)) {
+            path.push(parent.clone());
+        }
+
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth first search from every one of `starts` at once, for when every step costs the same.
+/// Returns the closest node that `goal` accepts.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if seen.insert(start.clone()) {
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, cost)) = queue.pop_front() {
+        if goal(&node) {
+            return Some(Found {
+                goal: node,
+                cost,
+                parents,
+            });
+        }
+
+        for next in neighbors(&node) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// The number of steps from the closest of `starts` to everything reachable
+pub fn bfs_all<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((node, cost)) = queue.pop_front() {
+        for next in neighbors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), cost + 1);
+                queue.push_back((next, cost + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// A node waiting in the priority queue, ordered so the `BinaryHeap` pops the lowest priority.
+/// Ties go to whichever has come further, which gets A* to the goal sooner.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .priority
+            .cmp(&self.priority)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+/// Everything a best first search learned, whether or not it found a goal
+struct Explored<N, C> {
+    found: Option<(N, C)>,
+    distances: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+fn best_first<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Explored<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut distances = HashMap::new();
+    let mut parents = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        distances.insert(start.clone(), C::default());
+        heap.push(Queued {
+            priority: heuristic(&start),
+            cost: C::default(),
+            node: start,
+        });
+    }
+
+    while let Some(Queued { cost, node, .. }) = heap.pop() {
+        // we already found a cheaper way here after this was queued
+        if distances.get(&node).is_some_and(|&best| cost > best) {
+            continue;
+        }
+
+        if goal(&node) {
+            return Explored {
+                found: Some((node, cost)),
+                distances,
+                parents,
+            };
+        }
+
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            if distances.get(&next).is_none_or(|&best| next_cost < best) {
+                distances.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                heap.push(Queued {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    Explored {
+        found: None,
+        distances,
+        parents,
+    }
+}
+
+/// Dijkstra's algorithm from every one of `starts` at once, where `neighbors` gives each next
+/// node along with the cost of the step. Returns the cheapest node that `goal` accepts.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbors, |_| C::default(), goal)
+}
+
+/// The cheapest cost from the closest of `starts` to everything reachable
+pub fn dijkstra_all<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, neighbors, |_| C::default(), |_| false).distances
+}
+
+/// A* search: Dijkstra guided by `heuristic`, an estimate of the remaining cost to a goal. The
+/// answer is only guaranteed to be the cheapest if the heuristic never overestimates.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<Found<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let explored = best_first(starts, neighbors, heuristic, goal);
+    explored.found.map(|(goal, cost)| Found {
+        goal,
+        cost,
+        parents: explored.parents,
+    })
+}
+
+/// The cheapest cost between every pair of `nodes` where the second can be reached from the
+/// first. Pairs that can't reach each other are left out.
+pub fn all_pairs<N, C, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> HashMap<(N, N), C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut result = HashMap::new();
+    for from in nodes {
+        for (to, cost) in dijkstra_all([from.clone()], &mut neighbors) {
+            result.insert((from.clone(), to), cost);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::{all_pairs, astar, bfs, bfs_all, dijkstra};
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#E";
+
+    type Pos = (usize, usize);
+
+    fn maze() -> Vec<Vec<char>> {
+        MAZE.lines().map(|l| l.chars().collect()).collect()
+    }
+
+    fn find(grid: &[Vec<char>], c: char) -> Pos {
+        (0..grid.len())
+            .flat_map(|y| (0..grid[0].len()).map(move |x| (x, y)))
+            .find(|&(x, y)| grid[y][x] == c)
+            .unwrap()
+    }
+
+    fn open_neighbors(grid: &[Vec<char>], (x, y): Pos) -> Vec<Pos> {
+        [(0, -1), (-1, 0), (0, 1), (1, 0)]
+            .into_iter()
+            .filter_map(|(dx, dy)| {
+                let nx = x.checked_add_signed(dx)?;
+                let ny = y.checked_add_signed(dy)?;
+                (*grid.get(ny)?.get(nx)? != '#').then_some((nx, ny))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bfs_finds_the_shortest_path() {
+        let grid = maze();
+        let (start, end) = (find(&grid, 'S'), find(&grid, 'E'));
+
+        let found = bfs([start], |&p| open_neighbors(&grid, p), |&p| p == end).unwrap();
+        assert_eq!(found.cost, 15);
+
+        let path = found.path();
+        assert_eq!(path.len(), found.cost + 1);
+        assert_eq!((path[0], path[path.len() - 1]), (start, end));
+        assert!(path
+            .windows(2)
+            .all(|w| open_neighbors(&grid, w[0]).contains(&w[1])));
+    }
+
+    #[test]
+    fn unreachable() {
+        let grid = maze();
+        let start = find(&grid, 'S');
+        assert!(bfs([start], |&p| open_neighbors(&grid, p), |&p| p == (3, 0)).is_none());
+
+        let distances = bfs_all([start], |&p| open_neighbors(&grid, p));
+        assert_eq!(distances.get(&start), Some(&0));
+        assert_eq!(distances.get(&(3, 0)), None);
+    }
+
+    #[test]
+    fn multiple_starts() {
+        let grid = maze();
+        let end = find(&grid, 'E');
+
+        // the start right next to the end should win
+        let starts = [find(&grid, 'S'), (7, 3)];
+        let found = bfs(starts, |&p| open_neighbors(&grid, p), |&p| p == end).unwrap();
+        assert_eq!(found.cost, 1);
+        assert_eq!(found.path(), vec![(7, 3), end]);
+    }
+
+    #[test]
+    fn weighted() {
+        // a -> b -> c is cheaper than going straight to c
+        let edges = |&n: &char| match n {
+            'a' => vec![('b', 1), ('c', 10)],
+            'b' => vec![('c', 2)],
+            _ => vec![],
+        };
+
+        let found = dijkstra(['a'], edges, |&n| n == 'c').unwrap();
+        assert_eq!(found.cost, 3);
+        assert_eq!(found.path(), vec!['a', 'b', 'c']);
+
+        let pairs = all_pairs(['a', 'b', 'c'], edges);
+        assert_eq!(pairs.get(&('a', 'c')), Some(&3));
+        assert_eq!(pairs.get(&('b', 'c')), Some(&2));
+        assert_eq!(pairs.get(&('c', 'c')), Some(&0));
+        assert_eq!(pairs.get(&('c', 'a')), None);
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let grid = maze();
+        let (start, end) = (find(&grid, 'S'), find(&grid, 'E'));
+        let neighbors = |&p: &Pos| open_neighbors(&grid, p).into_iter().map(|n| (n, 1));
+
+        let manhattan = |&(x, y): &Pos| x.abs_diff(end.0) + y.abs_diff(end.1);
+        let guided = astar([start], neighbors, manhattan, |&p| p == end).unwrap();
+        let plain = dijkstra([start], neighbors, |&p| p == end).unwrap();
+        assert_eq!(guided.cost, plain.cost);
+        assert_eq!(guided.cost, 15);
+    }
+}
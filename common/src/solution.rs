@@ -0,0 +1,157 @@
+use std::{
+    fmt::Display,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use rand::rngs::StdRng;
+
+use crate::{animate::Playback, answers::Answers, error::ParseError, render::Frame};
+
+/// One day's puzzle. Each day parses its raw input once and then solves both parts from that
+/// parsed representation. The input type is generic over the lifetime of the raw string so days
+/// can keep borrowing `&str` slices out of it.
+pub trait Solution {
+    /// The day of the advent calendar, 1 through 25
+    const DAY: u8;
+
+    type Input<'a>;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+    fn part1(input: &Self::Input<'_>) -> Self::Part1;
+    fn part2(input: &Self::Input<'_>) -> Self::Part2;
+
+    /// Make up a random but valid puzzle input, for finding out how the solution scales. What
+    /// `size` counts depends on the day (lines, monkeys, valves, the side of a grid...), and
+    /// whatever comes out has to get through [Solution::parse].
+    fn generate(rng: &mut StdRng, size: usize) -> String;
+
+    /// Write a parsed input back out as puzzle text, the inverse of [Solution::parse]. It doesn't
+    /// have to match the original text character for character, only parse back into the same
+    /// thing.
+    fn print(input: &Self::Input<'_>) -> String;
+
+    /// Draw how a part plays out, a frame at a time. Only the days that simulate something have
+    /// anything to draw, so by default there's nothing.
+    fn render(_input: &Self::Input<'_>, _part: Part) -> Option<Vec<Frame>> {
+        None
+    }
+
+    /// Play a part out step by step in the terminal. Like [Solution::render], only the days that
+    /// simulate something have anything to show.
+    fn animate<'a>(_input: &'a Self::Input<'_>, _part: Part) -> Option<Box<dyn Playback + 'a>> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl TryFrom<u8> for Part {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            x => Err(format!("there is no part {x}, only 1 and 2")),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let n: u8 = s.parse().map_err(|_| format!("{s} is not a part number"))?;
+        n.try_into()
+    }
+}
+
+impl Part {
+    /// The parts to run when `part` might narrow it down to just one
+    pub fn selected(part: Option<Part>) -> Vec<Part> {
+        match part {
+            Some(part) => vec![part],
+            None => vec![Part::One, Part::Two],
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A part's answer, whichever type the day works it out as
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    /// The text the answer displays as, minus any trailing whitespace on each line
+    pub text: String,
+    /// The name of the type it was worked out as, like `u32` or `Snafu`
+    pub type_name: &'static str,
+}
+
+impl Answer {
+    /// The answer as `value` displays, named after its type
+    pub fn new<T: Display>(value: &T) -> Answer {
+        let text = value.to_string();
+        let lines: Vec<&str> = text.trim_end().lines().map(str::trim_end).collect();
+        let path = std::any::type_name::<T>();
+        Answer {
+            text: lines.join("\n"),
+            type_name: path.rsplit("::").next().unwrap_or(path),
+        }
+    }
+}
+
+/// One part's answer, and how long it took to work out from the already parsed input
+#[derive(Debug, Clone)]
+pub struct Solved {
+    pub part: Part,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+fn timed<T: Display>(part: Part, solve: impl FnOnce() -> T) -> Solved {
+    let start = Instant::now();
+    let value = solve();
+    let elapsed = start.elapsed();
+    Solved {
+        part,
+        answer: Answer::new(&value),
+        elapsed,
+    }
+}
+
+/// Parse the raw input and solve the requested part, or both parts if `part` is None, timing each
+/// part
+pub fn solve<S: Solution>(raw: &str, part: Option<Part>) -> Result<Vec<Solved>, ParseError> {
+    let input = S::parse(raw)?;
+    Ok(Part::selected(part)
+        .into_iter()
+        .map(|part| match part {
+            Part::One => timed(part, || S::part1(&input)),
+            Part::Two => timed(part, || S::part2(&input)),
+        })
+        .collect())
+}
+
+/// Parse the raw input and solve the requested part, or both parts if `part` is None, keeping just
+/// the text of each answer
+pub fn run<S: Solution>(raw: &str, part: Option<Part>) -> Result<Answers, ParseError> {
+    let mut answers = Answers::default();
+    for solved in solve::<S>(raw, part)? {
+        answers.set(solved.part, solved.answer.text);
+    }
+    Ok(answers)
+}
@@ -0,0 +1,294 @@
+use std::collections::{hash_map, HashMap};
+
+use crate::point::{Dir4, Dir8, Point2};
+
+/// A signed position on a `SparseGrid`. `y` grows downwards, like the rows of a puzzle input.
+pub type Point = Point2<i64>;
+
+/// The smallest rectangle covering a set of points, inclusive on every side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min_x: i64,
+    pub max_x: i64,
+    pub min_y: i64,
+    pub max_y: i64,
+}
+
+impl Bounds {
+    /// A box holding just `p`
+    pub fn point(Point2 { x, y }: Point) -> Bounds {
+        Bounds {
+            min_x: x,
+            max_x: x,
+            min_y: y,
+            max_y: y,
+        }
+    }
+
+    /// The box around all of `points`, or None if there aren't any
+    pub fn around(points: impl IntoIterator<Item = Point>) -> Option<Bounds> {
+        points.into_iter().fold(None, |bounds, p| match bounds {
+            None => Some(Bounds::point(p)),
+            Some(mut bounds) => {
+                bounds.include(p);
+                Some(bounds)
+            }
+        })
+    }
+
+    /// Grow the box so it covers `p` too
+    pub fn include(&mut self, Point2 { x, y }: Point) {
+        self.min_x = self.min_x.min(x);
+        self.max_x = self.max_x.max(x);
+        self.min_y = self.min_y.min(y);
+        self.max_y = self.max_y.max(y);
+    }
+
+    pub fn contains(&self, Point2 { x, y }: Point) -> bool {
+        (self.min_x..=self.max_x).contains(&x) && (self.min_y..=self.max_y).contains(&y)
+    }
+
+    pub fn width(&self) -> u64 {
+        self.min_x.abs_diff(self.max_x) + 1
+    }
+
+    pub fn height(&self) -> u64 {
+        self.min_y.abs_diff(self.max_y) + 1
+    }
+
+    pub fn area(&self) -> u64 {
+        self.width() * self.height()
+    }
+}
+
+/// The points up, right, down and left of `p`
+pub fn adjacent4(p: Point) -> [Point; 4] {
+    Dir4::ALL.map(|dir| p + dir)
+}
+
+/// The points around `p` including diagonals, clockwise from straight up
+pub fn adjacent8(p: Point) -> [Point; 8] {
+    Dir8::ALL.map(|dir| p + dir)
+}
+
+/// A grid with no fixed size that only stores the cells that have something in them. Unlike
+/// `Map`, coordinates can go negative, and the bounds grow as cells get filled in.
+#[derive(Debug, Clone)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    bounds: Option<Bounds>,
+}
+
+/// Grids are equal when they have the same cells, however far their bounds have grown
+impl<T: PartialEq> PartialEq for SparseGrid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl<T: Eq> Eq for SparseGrid<T> {}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    /// Fill in a cell, returning whatever was there before
+    pub fn insert(&mut self, p: Point, data: T) -> Option<T> {
+        match &mut self.bounds {
+            Some(bounds) => bounds.include(p),
+            None => self.bounds = Some(Bounds::point(p)),
+        }
+
+        self.cells.insert(p, data)
+    }
+
+    /// Empty a cell. The bounds stay where they were, see `occupied_bounds` for a tight box.
+    pub fn remove(&mut self, p: Point) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Every filled cell, in no particular order
+    pub fn iter(&self) -> hash_map::Iter<'_, Point, T> {
+        self.cells.iter()
+    }
+
+    /// Every filled point, in no particular order
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Everything that has ever been inserted fits in here, or None if nothing has been
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    /// The smallest box around the cells that are filled right now
+    pub fn occupied_bounds(&self) -> Option<Bounds> {
+        Bounds::around(self.points())
+    }
+
+    /// The filled cells up, right, down and left of `p`
+    pub fn neighbors4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        adjacent4(p)
+            .into_iter()
+            .filter_map(|n| self.get(n).map(|data| (n, data)))
+    }
+
+    /// The filled cells around `p`, including diagonals
+    pub fn neighbors8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        adjacent8(p)
+            .into_iter()
+            .filter_map(|n| self.get(n).map(|data| (n, data)))
+    }
+
+    /// Draw the grid row by row over `bounds`, one character per cell
+    pub fn render_within(
+        &self,
+        bounds: Bounds,
+        cell: impl Fn(Point, Option<&T>) -> char,
+    ) -> String {
+        let mut s = String::new();
+        for y in bounds.min_y..=bounds.max_y {
+            for x in bounds.min_x..=bounds.max_x {
+                let p = Point::new(x, y);
+                s.push(cell(p, self.get(p)));
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    /// Draw everything that's been filled in, one character per cell
+    pub fn render(&self, cell: impl Fn(Point, Option<&T>) -> char) -> String {
+        match self.bounds {
+            Some(bounds) => self.render_within(bounds, cell),
+            None => String::new(),
+        }
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        for (p, data) in iter {
+            grid.insert(p, data);
+        }
+        grid
+    }
+}
+
+impl<T> Extend<(Point, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Point, T)>>(&mut self, iter: I) {
+        for (p, data) in iter {
+            self.insert(p, data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bounds, Point, SparseGrid};
+
+    #[test]
+    fn bounds_grow_on_demand() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Point::new(0, 0), 'a');
+        grid.insert(Point::new(-3, 2), 'b');
+        grid.insert(Point::new(4, -1), 'c');
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(
+            bounds,
+            Bounds {
+                min_x: -3,
+                max_x: 4,
+                min_y: -1,
+                max_y: 2
+            }
+        );
+        assert_eq!((bounds.width(), bounds.height(), bounds.area()), (8, 4, 32));
+
+        // removing doesn't shrink the bounds, but the occupied bounds are tight
+        grid.remove(Point::new(-3, 2));
+        assert_eq!(grid.bounds(), Some(bounds));
+        assert_eq!(
+            grid.occupied_bounds(),
+            Some(Bounds {
+                min_x: 0,
+                max_x: 4,
+                min_y: -1,
+                max_y: 0
+            })
+        );
+    }
+
+    #[test]
+    fn equality_ignores_bounds() {
+        let mut grid = SparseGrid::new();
+        grid.insert(Point::new(0, 0), 'a');
+        let before = grid.clone();
+
+        grid.insert(Point::new(5, 5), 'b');
+        assert_ne!(grid, before);
+        grid.remove(Point::new(5, 5));
+        assert_ne!(grid.bounds(), before.bounds());
+        assert_eq!(grid, before);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid: SparseGrid<char> = [((0, 0), 'x'), ((1, 0), 'r'), ((1, 1), 'd'), ((-1, -1), 'n')]
+            .into_iter()
+            .map(|(p, c)| (Point::from(p), c))
+            .collect();
+
+        let origin = Point::new(0, 0);
+        let mut four: Vec<char> = grid.neighbors4(origin).map(|(_, c)| *c).collect();
+        four.sort();
+        assert_eq!(four, vec!['r']);
+
+        let mut eight: Vec<char> = grid.neighbors8(origin).map(|(_, c)| *c).collect();
+        eight.sort();
+        assert_eq!(eight, vec!['d', 'n', 'r']);
+    }
+
+    #[test]
+    fn render() {
+        let grid: SparseGrid<()> = [(Point::new(-1, 0), ()), (Point::new(1, 1), ())]
+            .into_iter()
+            .collect();
+        let rendered = grid.render(|_, cell| if cell.is_some() { '#' } else { '.' });
+        assert_eq!(rendered, "#..\n..#\n");
+    }
+}
@@ -1,14 +1,26 @@
 use std::fmt::Debug;
+use std::path::Path;
 use std::str::FromStr;
 
+use crate::{answers::Answers, generate::seeded, run, Part, Solution};
+
+// the property tests need proptest, which only the days' tests should have to build
+#[cfg(feature = "test-util")]
+mod properties;
+#[cfg(feature = "test-util")]
+pub use properties::{check_arbitrary_input, check_round_trip};
+
+/// Every day's main example, the one from the puzzle text
+const EXAMPLE: &str = "./examples/example.txt";
+
 pub fn get_raw_input() -> String {
-    crate::util::get_raw_input("./test.txt")
+    crate::util::get_raw_input(EXAMPLE)
 }
 pub fn get_input<T, F>(f: F) -> Vec<T>
 where
     F: Fn(&str) -> T,
 {
-    crate::util::get_input("./test.txt", f)
+    crate::util::get_input(EXAMPLE, f)
 }
 
 pub fn get_input_strings() -> Vec<String> {
@@ -20,5 +32,87 @@ where
     T: FromStr + Debug,
     <T as FromStr>::Err: Debug,
 {
-    crate::util::get_numbers("./test.txt")
+    crate::util::get_numbers(EXAMPLE)
+}
+
+/// The text of `examples/NAME.txt`
+pub fn get_example(name: &str) -> String {
+    crate::util::get_raw_input(&format!("./examples/{name}.txt"))
+}
+
+/// Solve one part of an example and check it against what's recorded for it in
+/// `examples/NAME.answers.txt`
+pub fn check_example<S: Solution>(name: &str, part: Part) {
+    let path = format!("./examples/{name}.answers.txt");
+    let expected = Answers::load(Path::new(&path))
+        .unwrap_or_else(|e| panic!("can't read {path}: {e}"))
+        .get(part)
+        .unwrap_or_else(|| panic!("{path} has no answer for part {part}"))
+        .to_owned();
+
+    let answers = run::<S>(&get_example(name), Some(part))
+        .unwrap_or_else(|e| panic!("day {} example {name} doesn't parse: {e}", S::DAY));
+    assert_eq!(
+        answers.get(part),
+        Some(expected.as_str()),
+        "day {} example {name} part {part}",
+        S::DAY
+    );
+}
+
+/// Check that an example without any recorded answers at least parses
+pub fn check_example_parses<S: Solution>(name: &str) {
+    if let Err(e) = S::parse(&get_example(name)) {
+        panic!("day {} example {name} doesn't parse: {e}", S::DAY);
+    }
+}
+
+/// A test for each example in the day's `examples` directory and each part with a recorded
+/// answer, named after the example and the part. The day has to use the shared build script
+/// (`build = "../common/build_examples.rs"`), which finds the examples.
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod test {
+///     use crate::Day09;
+///
+///     common::examples!(Day09);
+/// }
+/// ```
+#[macro_export]
+macro_rules! examples {
+    ($day:ident) => {
+        mod examples {
+            #[allow(dead_code)] // there might not be any examples yet
+            type Day = super::$day;
+
+            include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+        }
+    };
+}
+
+/// The biggest generated inputs [check_generator] solves as well as parses, small enough for any
+/// day to get through quickly without optimizations
+const SOLVED_SIZE: usize = 3;
+
+/// Generate inputs of each size from a handful of seeds, checking that the day's parser accepts
+/// every one of them and that the same seed always makes the same input. The small ones get both
+/// parts solved too, so the generator can't make inputs the solution chokes on.
+pub fn check_generator<S: Solution>(sizes: &[usize]) {
+    for &size in sizes {
+        for seed in 0..4 {
+            let input = S::generate(&mut seeded(seed), size);
+            if let Err(e) = S::parse(&input) {
+                panic!("generated day {} input of size {size} from seed {seed} doesn't parse: {e}\n{input}", S::DAY);
+            }
+            assert_eq!(input, S::generate(&mut seeded(seed), size));
+
+            if size <= SOLVED_SIZE && std::panic::catch_unwind(|| run::<S>(&input, None)).is_err() {
+                panic!(
+                    "day {} panicked solving a generated input of size {size} from seed {seed}\n{input}",
+                    S::DAY
+                );
+            }
+        }
+    }
 }
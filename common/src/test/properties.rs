@@ -0,0 +1,141 @@
+//! Property tests that every day runs against its parser and printer
+
+use std::fmt::Debug;
+
+use proptest::{
+    prelude::*,
+    sample::{select, Index},
+    test_runner::{Config, TestCaseError, TestRunner},
+};
+
+use crate::{generate::seeded, Solution};
+
+/// Write the tests every day runs on its generator, printer and parser: `generated` with
+/// [check_generator](crate::test::check_generator) at each of the sizes, then `round_trip` and
+/// `arbitrary_input`
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod test {
+///     use crate::Day01;
+///
+///     common::examples!(Day01);
+///     common::properties!(Day01, [1, 3, 50]);
+/// }
+/// ```
+#[macro_export]
+macro_rules! properties {
+    ($day:ident, [$($size:expr),* $(,)?]) => {
+        #[test]
+        fn generated() {
+            $crate::test::check_generator::<$day>(&[$($size),*]);
+        }
+
+        #[test]
+        fn round_trip() {
+            $crate::test::check_round_trip::<$day>();
+        }
+
+        #[test]
+        fn arbitrary_input() {
+            $crate::test::check_arbitrary_input::<$day>();
+        }
+    };
+}
+
+fn runner(cases: u32) -> TestRunner {
+    TestRunner::new(Config {
+        cases,
+        ..Config::default()
+    })
+}
+
+/// Parse generated inputs, print them back out and check that what was printed parses into the
+/// same thing as the original did
+pub fn check_round_trip<S: Solution>()
+where
+    for<'a> S::Input<'a>: PartialEq + Debug,
+{
+    // both parsed from strings living equally long, so there's one input type to compare
+    fn same<'a, S: Solution>(raw: &'a str, printed: &'a str) -> Result<(), TestCaseError>
+    where
+        for<'b> S::Input<'b>: PartialEq + Debug,
+    {
+        let original = S::parse(raw).map_err(|e| TestCaseError::fail(e.to_string()))?;
+        let again = S::parse(printed).map_err(|e| {
+            TestCaseError::fail(format!("the printed input doesn't parse: {e}\n{printed}"))
+        })?;
+        prop_assert_eq!(original, again, "after being printed as\n{}", printed);
+        Ok(())
+    }
+
+    let result = runner(64).run(&(any::<u64>(), 1..30_usize), |(seed, size)| {
+        let raw = S::generate(&mut seeded(seed), size);
+        let parsed = S::parse(&raw).map_err(|e| TestCaseError::fail(e.to_string()))?;
+        let printed = S::print(&parsed);
+        same::<S>(&raw, &printed)
+    });
+    if let Err(e) = result {
+        panic!("day {} doesn't print what it parsed: {e}", S::DAY);
+    }
+}
+
+/// Characters that mean something to at least one of the days' parsers, so changing them is
+/// more likely to get somewhere than changing to any old character
+const SIGNIFICANT: &str = "0123456789-+*/=,.:;#<>^v[] \nabcdexyzAZLRUD";
+
+/// One change to a piece of text: remove, insert or replace a character at some position, or
+/// cut the text off there
+fn edit() -> impl Strategy<Value = (Index, u8, char)> {
+    let replacement = prop_oneof![
+        3 => select(SIGNIFICANT.chars().collect::<Vec<_>>()),
+        1 => any::<char>(),
+    ];
+    (any::<Index>(), 0..4_u8, replacement)
+}
+
+fn apply_edits(text: &str, edits: &[(Index, u8, char)]) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    for (at, kind, c) in edits {
+        let at = at.index(chars.len() + 1);
+        match kind {
+            0 if at < chars.len() => {
+                chars.remove(at);
+            }
+            1 => chars.insert(at, *c),
+            2 if at < chars.len() => chars[at] = *c,
+            _ => chars.truncate(at),
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Parse the text, failing the test case with the text that did it if the parser panics
+fn parse_without_panic<S: Solution>(text: &str) -> Result<(), TestCaseError> {
+    match std::panic::catch_unwind(|| S::parse(text).map(|_| ())) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(TestCaseError::fail(format!("panicked parsing\n{text}"))),
+    }
+}
+
+/// Feed the parser random text, and generated inputs with a few characters changed, checking that
+/// it always gives back an error for what it can't make sense of rather than panicking
+pub fn check_arbitrary_input<S: Solution>() {
+    let random = runner(256).run(&"\\PC{0,200}", |text| parse_without_panic::<S>(&text));
+    if let Err(e) = random {
+        panic!("day {} panicked parsing random text: {e}", S::DAY);
+    }
+
+    let edited = (
+        any::<u64>(),
+        1..20_usize,
+        prop::collection::vec(edit(), 1..4),
+    );
+    let edited = runner(256).run(&edited, |(seed, size, edits)| {
+        let text = apply_edits(&S::generate(&mut seeded(seed), size), &edits);
+        parse_without_panic::<S>(&text)
+    });
+    if let Err(e) = edited {
+        panic!("day {} panicked parsing an edited input: {e}", S::DAY);
+    }
+}
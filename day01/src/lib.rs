@@ -0,0 +1,97 @@
+//! Day 1: Calorie Counting
+//!
+//! Each elf's snacks come as a group of calorie counts; part 1 wants the biggest total and part 2
+//! the sum of the three biggest.
+
+#![warn(missing_docs)]
+
+use std::cmp::Reverse;
+
+use common::{
+    error::ParseError,
+    generate::lines,
+    nom::{parse_complete, sections},
+    Solution,
+};
+use nom::{
+    character::complete::{line_ending, u32 as nom_u32},
+    combinator::map,
+    multi::separated_list1,
+};
+use rand::{rngs::StdRng, Rng};
+
+/// Totals up each elf's calories from the lists between blank lines
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input<'a> = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_calorie_groups(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+fn parse_calorie_groups(s: &str) -> Result<Vec<u32>, ParseError> {
+    let mut v = parse_complete(
+        s,
+        sections(map(separated_list1(line_ending, nom_u32), |group| {
+            group.iter().sum()
+        })),
+    )?;
+
+    v.sort_by_key(|x| Reverse(*x));
+    Ok(v)
+}
+
+/// `size` elves, each carrying a handful of snacks
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let snacks = rng.gen_range(1..=8);
+            lines((0..snacks).map(|_| rng.gen_range(1000..=9999)))
+        })
+        .collect();
+    elves.join("\n")
+}
+
+/// Every elf's total as a single snack, which adds up to the same thing
+fn print(totals: &[u32]) -> String {
+    let elves: Vec<String> = totals.iter().map(|total| format!("{total}\n")).collect();
+    elves.join("\n")
+}
+
+fn problem1(cal: &[u32]) -> u32 {
+    *cal.first().unwrap()
+}
+
+fn problem2(cal: &[u32]) -> u32 {
+    cal.iter().take(3).sum()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day01;
+
+    common::examples!(Day01);
+    common::properties!(Day01, [1, 3, 50]);
+}
@@ -0,0 +1,209 @@
+//! Day 2: Rock Paper Scissors
+//!
+//! A strategy guide of rounds, each an opponent's [Hand] and a [Code] that part 1 reads as our
+//! hand and part 2 as the outcome we're after.
+
+#![warn(missing_docs)]
+
+use common::{
+    error::ParseError,
+    generate::lines,
+    nom::{parse_lines, IResult},
+    Solution,
+};
+use nom::{
+    branch::alt,
+    character::complete::{char, space1},
+    combinator::map,
+    error::context,
+    sequence::separated_pair,
+};
+use rand::{rngs::StdRng, Rng};
+
+/// Scores a strategy guide of `A Y` style rounds
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input<'a> = Input;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+/// Each round of the guide, in order
+pub type Input = Vec<(Hand, Code)>;
+
+// using nom is overkill for this, but I figured there's gonna be a lot more parsing later so might as well
+// get some practice in
+fn parse_round(s: &str) -> IResult<&str, (Hand, Code)> {
+    let hand = alt((
+        map(char('A'), |_| Hand::Rock),
+        map(char('B'), |_| Hand::Paper),
+        map(char('C'), |_| Hand::Scissors),
+    ));
+
+    let code = alt((
+        map(char('X'), |_| Code::X),
+        map(char('Y'), |_| Code::Y),
+        map(char('Z'), |_| Code::Z),
+    ));
+
+    context("round", separated_pair(hand, space1, code))(s)
+}
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_lines(input, parse_round)
+}
+
+/// `size` rounds of random hands and codes
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    lines((0..size.max(1)).map(|_| {
+        let hand = rng.gen_range('A'..='C');
+        let code = rng.gen_range('X'..='Z');
+        format!("{hand} {code}")
+    }))
+}
+
+fn print(rounds: &Input) -> String {
+    lines(rounds.iter().map(|(hand, code)| {
+        let hand = match hand {
+            Hand::Rock => 'A',
+            Hand::Paper => 'B',
+            Hand::Scissors => 'C',
+        };
+        let code = match code {
+            Code::X => 'X',
+            Code::Y => 'Y',
+            Code::Z => 'Z',
+        };
+        format!("{hand} {code}")
+    }))
+}
+
+fn problem1(rounds: &Input) -> u32 {
+    rounds
+        .iter()
+        .map(|(o, code)| Hand::from(*code).score(*o))
+        .sum()
+}
+
+fn problem2(rounds: &Input) -> u32 {
+    rounds
+        .iter()
+        .map(|(o, code)| Strategy::from(*code).get_hand(*o).score(*o))
+        .sum()
+}
+
+/// The second column of the strategy guide, which means something different in each part
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Code {
+    /// Rock, or a loss
+    X,
+    /// Paper, or a draw
+    Y,
+    /// Scissors, or a win
+    Z,
+}
+
+impl From<Code> for Hand {
+    fn from(value: Code) -> Self {
+        match value {
+            Code::X => Hand::Rock,
+            Code::Y => Hand::Paper,
+            Code::Z => Hand::Scissors,
+        }
+    }
+}
+
+impl From<Code> for Strategy {
+    fn from(value: Code) -> Self {
+        match value {
+            Code::X => Strategy::Lose,
+            Code::Y => Strategy::Draw,
+            Code::Z => Strategy::Win,
+        }
+    }
+}
+
+/// What a player throws, the first column being the opponent's
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
+    /// Beats scissors, written `A`
+    Rock,
+    /// Beats rock, written `B`
+    Paper,
+    /// Beats paper, written `C`
+    Scissors,
+}
+
+#[derive(Clone, Copy)]
+enum Strategy {
+    Win = 1,
+    Lose = -1,
+    Draw = 0,
+}
+
+impl Strategy {
+    fn get_hand(&self, other: Hand) -> Hand {
+        // there's definitely a way to mod math this one too, but I am laaaazy
+        match (other, self) {
+            (Hand::Rock, Strategy::Win) => Hand::Paper,
+            (Hand::Rock, Strategy::Lose) => Hand::Scissors,
+            (Hand::Rock, Strategy::Draw) => Hand::Rock,
+            (Hand::Paper, Strategy::Win) => Hand::Scissors,
+            (Hand::Paper, Strategy::Lose) => Hand::Rock,
+            (Hand::Paper, Strategy::Draw) => Hand::Paper,
+            (Hand::Scissors, Strategy::Win) => Hand::Rock,
+            (Hand::Scissors, Strategy::Lose) => Hand::Paper,
+            (Hand::Scissors, Strategy::Draw) => Hand::Scissors,
+        }
+    }
+}
+
+impl Hand {
+    /// Our score for a round where we throw this against `other`: the hand's own value (1 for
+    /// rock up to 3 for scissors) plus 0, 3 or 6 for losing, drawing or winning
+    pub fn score(&self, other: Hand) -> u32 {
+        let s = *self as u32;
+        let o = other as u32;
+
+        let number_score = if o == (s + 1) % 3 {
+            0
+        } else if o == s {
+            3
+        } else {
+            6
+        };
+
+        number_score + s + 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day02;
+
+    common::examples!(Day02);
+    common::properties!(Day02, [1, 50]);
+}
@@ -0,0 +1,185 @@
+//! Day 3: Rucksack Reorganization
+//!
+//! Each [Rucksack] has two compartments; part 1 scores the item in both, part 2 the badge each
+//! group of three has in common.
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+
+use common::{error::ParseError, generate::lines, nom::parse_lines, Solution};
+use nom::{character::complete::alpha1, combinator::map, error::context};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+/// Reads a rucksack per line and sums item priorities
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input<'a> = Vec<Rucksack>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        get_rucksacks(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+/// The items in one rucksack, split between its two compartments
+#[derive(Debug, PartialEq, Eq)]
+pub struct Rucksack {
+    compartment1: HashSet<char>,
+    compartment2: HashSet<char>,
+}
+
+impl Rucksack {
+    /// A rucksack from its line of items, the first half in one compartment and the second half
+    /// in the other
+    pub fn new(s: &str) -> Rucksack {
+        let (c1, c2) = s.split_at(s.len() / 2);
+        Rucksack {
+            compartment1: HashSet::from_iter(c1.chars()),
+            compartment2: HashSet::from_iter(c2.chars()),
+        }
+    }
+
+    /// The item that's in both compartments. Panics if there isn't one.
+    pub fn get_shared(&self) -> &char {
+        self.compartment1
+            .intersection(&self.compartment2)
+            .next()
+            .unwrap()
+    }
+
+    /// Everything in the rucksack, whichever compartment it's in
+    pub fn get_all_items(&self) -> HashSet<&char> {
+        self.compartment1.union(&self.compartment2).collect()
+    }
+
+    /// The item this rucksack has in common with two others, the group's badge. Panics if there
+    /// isn't one.
+    pub fn get_badge<'a>(&'a self, other1: &'a Rucksack, other2: &'a Rucksack) -> &'a char {
+        let s0: HashSet<_> = self.get_all_items();
+        let s1: HashSet<_> = other1.get_all_items();
+        let s2: HashSet<_> = other2.get_all_items();
+
+        let common1: HashSet<_> = s0.intersection(&s1).collect();
+        let common2: HashSet<_> = s1.intersection(&s2).collect();
+
+        common1.intersection(&common2).next().unwrap()
+    }
+}
+
+/// An item's priority: 1 to 26 for `a` to `z`, then 27 to 52 for `A` to `Z`
+pub fn prioritize(c: &char) -> u32 {
+    (*c as u32) - if c.is_lowercase() { 96 } else { 38 }
+}
+
+fn get_rucksacks(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    parse_lines(input, context("rucksack", map(alpha1, Rucksack::new)))
+}
+
+/// Each compartment's items in order, repeated to make the two halves the same length. A
+/// rucksack with a single item has nothing in its first compartment.
+fn print(rucksacks: &[Rucksack]) -> String {
+    lines(rucksacks.iter().map(|r| {
+        let mut left: Vec<char> = r.compartment1.iter().copied().collect();
+        let mut right: Vec<char> = r.compartment2.iter().copied().collect();
+        left.sort();
+        right.sort();
+        if left.is_empty() {
+            return right.into_iter().collect();
+        }
+
+        let len = left.len().max(right.len());
+        let left = left.iter().cycle().take(len);
+        let right = right.iter().cycle().take(len);
+        left.chain(right).collect::<String>()
+    }))
+}
+
+/// `size` rucksacks, rounded up to whole groups of three. Each rucksack has exactly one item type
+/// in both compartments, and each group has exactly one badge that all three of them carry.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let all_items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+
+    let mut rucksacks = vec![];
+    for _ in 0..size.div_ceil(3).max(1) {
+        let mut items = all_items.clone();
+        items.shuffle(rng);
+        let (&badge, rest) = items.split_first().unwrap();
+
+        // each elf gets their own items besides the badge, so the badge is all they have in common
+        for own in rest.chunks(rest.len() / 3).take(3) {
+            let (left_only, rest) = own.split_at(6);
+            let (right_only, rest) = rest.split_at(6);
+
+            // what's in both compartments is either the badge or one of their own items, and if
+            // it's not the badge, the badge still has to go in one side
+            let shared = if rng.gen_bool(1.0 / 3.0) {
+                badge
+            } else {
+                rest[0]
+            };
+            let (mut left, mut right) = (vec![shared], vec![shared]);
+            if shared != badge {
+                if rng.gen() {
+                    left.push(badge);
+                } else {
+                    right.push(badge);
+                }
+            }
+
+            let len = rng.gen_range(4..=16);
+            for (half, only) in [(&mut left, left_only), (&mut right, right_only)] {
+                while half.len() < len {
+                    half.push(*only.choose(rng).unwrap());
+                }
+                half.shuffle(rng);
+            }
+            rucksacks.push(left.into_iter().chain(right).collect::<String>());
+        }
+    }
+    lines(rucksacks)
+}
+
+fn problem1(rucksacks: &[Rucksack]) -> u32 {
+    rucksacks.iter().map(|r| prioritize(r.get_shared())).sum()
+}
+
+fn problem2(rucksacks: &[Rucksack]) -> u32 {
+    rucksacks
+        .chunks(3)
+        .map(|g| {
+            let [g0, g1, g2] = g else {
+                panic!("not a valid group");
+            };
+            prioritize(g0.get_badge(g1, g2))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day03;
+
+    common::examples!(Day03);
+    common::properties!(Day03, [1, 3, 10, 50]);
+}
@@ -0,0 +1,127 @@
+//! Day 4: Camp Cleanup
+//!
+//! Pairs of section [Assignment]s; part 1 counts pairs where one contains the other, part 2 pairs
+//! that overlap at all.
+
+#![warn(missing_docs)]
+
+use common::{
+    error::ParseError,
+    generate::lines,
+    interval::Interval,
+    nom::{parse_lines, IResult},
+    Solution,
+};
+
+use nom::{
+    character::complete::{char, u32 as nom_u32},
+    combinator::{map, map_opt},
+    error::context,
+    sequence::separated_pair,
+};
+use rand::{rngs::StdRng, Rng};
+
+/// Reads `2-4,6-8` style pairs of section ranges
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input<'a> = Vec<Assignment>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_assignments(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+fn parse_sections(s: &str) -> IResult<&str, Interval<u32>> {
+    context(
+        "sections",
+        map_opt(
+            separated_pair(nom_u32, char('-'), nom_u32),
+            |(start, end)| Interval::new(start, end),
+        ),
+    )(s)
+}
+
+/// The sections assigned to a pair of elves
+#[derive(Debug, PartialEq, Eq)]
+pub struct Assignment {
+    first: Interval<u32>,
+    second: Interval<u32>,
+}
+impl Assignment {
+    /// Whether one elf's sections contain all of the other's
+    pub fn is_full_overlap(&self) -> bool {
+        self.first.contains_interval(&self.second) || self.second.contains_interval(&self.first)
+    }
+
+    /// Whether the two elves share any section at all
+    pub fn is_any_overlap(&self) -> bool {
+        self.first.overlaps(&self.second)
+    }
+
+    fn parse(s: &str) -> IResult<&str, Assignment> {
+        map(
+            separated_pair(parse_sections, char(','), parse_sections),
+            |(first, second)| Assignment { first, second },
+        )(s)
+    }
+}
+
+fn parse_assignments(input: &str) -> Result<Vec<Assignment>, ParseError> {
+    parse_lines(input, Assignment::parse)
+}
+
+/// `size` pairs of elves, each assigned somewhere in sections 1 to 99
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut sections = || {
+        let start = rng.gen_range(1..=99);
+        let end = rng.gen_range(start..=99);
+        format!("{start}-{end}")
+    };
+    lines((0..size.max(1)).map(|_| format!("{},{}", sections(), sections())))
+}
+
+fn print(assignments: &[Assignment]) -> String {
+    let sections = |i: &Interval<u32>| format!("{}-{}", i.start(), i.end());
+    lines(
+        assignments
+            .iter()
+            .map(|a| format!("{},{}", sections(&a.first), sections(&a.second))),
+    )
+}
+
+fn problem1(assignments: &[Assignment]) -> u32 {
+    assignments.iter().filter(|x| x.is_full_overlap()).count() as u32
+}
+
+fn problem2(assignments: &[Assignment]) -> u32 {
+    assignments.iter().filter(|x| x.is_any_overlap()).count() as u32
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day04;
+
+    common::examples!(Day04);
+    common::properties!(Day04, [1, 50]);
+}
@@ -0,0 +1,384 @@
+//! Cranes, and running the moves with one.
+//!
+//! The puzzle has two cranes, the [CrateMover9000] that lifts one crate at a time and the
+//! [CrateMover9001] that lifts any number at once. [LiftsUpTo] is anything in between, and any
+//! other [Crane] can be plugged in too:
+//!
+//! ```
+//! use common::Solution;
+//! use day05::{crane::{CrateMover9000, LiftsUpTo, Simulator}, Day05};
+//!
+//! let input = Day05::parse("[A]    \n[B]    \n[C] [D]\n 1   2 \n\nmove 3 from 1 to 2\n").unwrap();
+//!
+//! let mut one_at_a_time = Simulator::new(CrateMover9000, input.stacks());
+//! one_at_a_time.run(input.moves()).unwrap();
+//! assert_eq!(one_at_a_time.stacks()[1], ["D", "A", "B", "C"]);
+//!
+//! let mut two_at_a_time = Simulator::new(LiftsUpTo::new(2).unwrap(), input.stacks());
+//! two_at_a_time.run(input.moves()).unwrap();
+//! assert_eq!(two_at_a_time.stacks()[1], ["D", "B", "A", "C"]);
+//! ```
+
+use std::{error::Error, fmt::Display, num::NonZeroUsize};
+
+use common::{animate::Playback, error::ParseError};
+
+use crate::{draw, Move, Stack};
+
+/// Something that moves crates from the top of one stack to the top of another
+pub trait Crane {
+    /// The most crates it can lift at once
+    fn capacity(&self) -> usize;
+
+    /// Where `lifted`, the crates taken off the top of a stack (bottom first), end up on the stack
+    /// they're moved to, bottom first. By default it lifts as many off the top as it can each
+    /// trip, and puts them down without turning them over.
+    fn carry<'a>(&self, lifted: Stack<'a>) -> Stack<'a> {
+        lifted
+            .rchunks(self.capacity().max(1))
+            .flatten()
+            .copied()
+            .collect()
+    }
+}
+
+/// Part 1's crane, which lifts one crate at a time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn capacity(&self) -> usize {
+        1
+    }
+}
+
+/// Part 2's crane, which lifts all the crates it's moving at once
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn capacity(&self) -> usize {
+        usize::MAX
+    }
+}
+
+/// A crane that lifts some number of crates at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiftsUpTo(NonZeroUsize);
+
+impl LiftsUpTo {
+    /// A crane that lifts up to `capacity` crates, unless that's none
+    pub fn new(capacity: usize) -> Option<LiftsUpTo> {
+        NonZeroUsize::new(capacity).map(LiftsUpTo)
+    }
+}
+
+impl Crane for LiftsUpTo {
+    fn capacity(&self) -> usize {
+        self.0.get()
+    }
+}
+
+/// A move that can't be made
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoveError {
+    /// A stack that isn't there
+    NoSuchStack {
+        /// The line of the move
+        line: usize,
+        /// The stack's number, counting from 1 like the input does
+        stack: usize,
+    },
+    /// More crates than the stack has on it by then
+    NotEnoughCrates {
+        /// The line of the move
+        line: usize,
+        /// The stack's number, counting from 1 like the input does
+        stack: usize,
+        /// How many the move takes
+        wanted: usize,
+        /// How many there are
+        there: usize,
+    },
+}
+
+impl MoveError {
+    /// The line of the move
+    pub fn line(&self) -> usize {
+        match self {
+            MoveError::NoSuchStack { line, .. } | MoveError::NotEnoughCrates { line, .. } => *line,
+        }
+    }
+
+    /// What's wrong with the move, without where it is
+    fn problem(&self) -> String {
+        match self {
+            MoveError::NoSuchStack { stack, .. } => format!("there's no stack {stack}"),
+            MoveError::NotEnoughCrates {
+                stack,
+                wanted,
+                there,
+                ..
+            } => format!("can't move {wanted} crates off stack {stack}, which only has {there}"),
+        }
+    }
+
+    /// The error pointing at the move in `input`, the text it was parsed from
+    pub fn into_parse_error(self, input: &str) -> ParseError {
+        ParseError::on_line(input, self.line(), self.problem())
+    }
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line(), self.problem())
+    }
+}
+
+impl Error for MoveError {}
+
+/// The stacks at one point of a simulation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot<'a> {
+    /// The move that was just made, if any has been
+    pub after: Option<Move>,
+    /// Every stack, bottom first
+    pub stacks: Vec<Stack<'a>>,
+}
+
+/// Stacks of crates, and a crane to make moves on them with
+#[derive(Debug, Clone)]
+pub struct Simulator<'a, C> {
+    crane: C,
+    stacks: Vec<Stack<'a>>,
+    /// Every snapshot so far, if they're being kept
+    history: Option<Vec<Snapshot<'a>>>,
+}
+
+impl<'a, C: Crane> Simulator<'a, C> {
+    /// A simulator starting from `stacks`
+    pub fn new(crane: C, stacks: &[Stack<'a>]) -> Simulator<'a, C> {
+        Simulator {
+            crane,
+            stacks: stacks.to_vec(),
+            history: None,
+        }
+    }
+
+    /// A simulator starting from `stacks`, which keeps a [Snapshot] from before the first move
+    /// and after every one, to replay later
+    pub fn recording(crane: C, stacks: &[Stack<'a>]) -> Simulator<'a, C> {
+        let mut simulator = Simulator::new(crane, stacks);
+        simulator.history = Some(vec![Snapshot {
+            after: None,
+            stacks: simulator.stacks.clone(),
+        }]);
+        simulator
+    }
+
+    /// Make a move, unless it can't be made, in which case nothing changes
+    pub fn step(&mut self, m: &Move) -> Result<(), MoveError> {
+        for stack in [m.from, m.to] {
+            if stack >= self.stacks.len() {
+                return Err(MoveError::NoSuchStack {
+                    line: m.line,
+                    stack: stack + 1,
+                });
+            }
+        }
+        let from = &mut self.stacks[m.from];
+        if m.count > from.len() {
+            return Err(MoveError::NotEnoughCrates {
+                line: m.line,
+                stack: m.from + 1,
+                wanted: m.count,
+                there: from.len(),
+            });
+        }
+
+        let lifted = from.split_off(from.len() - m.count);
+        let landed = self.crane.carry(lifted);
+        self.stacks[m.to].extend(landed);
+
+        if let Some(history) = &mut self.history {
+            history.push(Snapshot {
+                after: Some(m.clone()),
+                stacks: self.stacks.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Make every move in turn, stopping at the first that can't be made
+    pub fn run(&mut self, moves: &[Move]) -> Result<(), MoveError> {
+        moves.iter().try_for_each(|m| self.step(m))
+    }
+
+    /// Every stack as it is now, bottom first
+    pub fn stacks(&self) -> &[Stack<'a>] {
+        &self.stacks
+    }
+
+    /// The crate on top of each stack, or a space for an empty one
+    pub fn tops(&self) -> String {
+        self.stacks
+            .iter()
+            .map(|stack| stack.last().copied().unwrap_or(" "))
+            .collect()
+    }
+
+    /// Every [Snapshot] so far, which there are only any of when it's [Simulator::recording]
+    pub fn history(&self) -> &[Snapshot<'a>] {
+        self.history.as_deref().unwrap_or_default()
+    }
+
+    /// Stop simulating, keeping the history
+    pub fn into_history(self) -> Vec<Snapshot<'a>> {
+        self.history.unwrap_or_default()
+    }
+}
+
+/// A recorded history played back
+pub(crate) struct Replay<'a> {
+    history: Vec<Snapshot<'a>>,
+    at: usize,
+}
+
+impl<'a> Replay<'a> {
+    pub(crate) fn new(history: Vec<Snapshot<'a>>) -> Replay<'a> {
+        Replay { history, at: 0 }
+    }
+}
+
+impl Playback for Replay<'_> {
+    fn restart(&mut self) {
+        self.at = 0;
+    }
+
+    fn step(&mut self) -> bool {
+        let more = self.at + 1 < self.history.len();
+        if more {
+            self.at += 1;
+        }
+        more
+    }
+
+    fn draw(&self) -> String {
+        let snapshot = &self.history[self.at];
+        let after = match &snapshot.after {
+            Some(m) => format!("{m}\n"),
+            None => "\n".to_owned(),
+        };
+        draw(&snapshot.stacks) + &after
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{animate::Playback, test::get_raw_input, Solution};
+
+    use super::{Crane, CrateMover9000, CrateMover9001, LiftsUpTo, MoveError, Replay, Simulator};
+    use crate::{Day05, Input, Move};
+
+    fn tops(crane: impl Crane, input: &Input) -> String {
+        let mut simulator = Simulator::new(crane, input.stacks());
+        simulator.run(input.moves()).unwrap();
+        simulator.tops()
+    }
+
+    #[test]
+    fn cranes() {
+        let input = get_raw_input();
+        let input = Day05::parse(&input).unwrap();
+        assert_eq!(tops(CrateMover9000, &input), "CMZ");
+        assert_eq!(tops(CrateMover9001, &input), "MCD");
+        assert_eq!(tops(LiftsUpTo::new(1).unwrap(), &input), "CMZ");
+        assert_eq!(tops(LiftsUpTo::new(3).unwrap(), &input), "MCD");
+        assert_eq!(tops(LiftsUpTo::new(2).unwrap(), &input), "MCZ");
+        assert_eq!(LiftsUpTo::new(0), None);
+
+        let lifted = vec!["a", "b", "c", "d", "e"];
+        assert_eq!(
+            LiftsUpTo::new(2).unwrap().carry(lifted.clone()),
+            ["d", "e", "b", "c", "a"]
+        );
+        assert_eq!(
+            CrateMover9000.carry(lifted.clone()),
+            ["e", "d", "c", "b", "a"]
+        );
+        assert_eq!(CrateMover9001.carry(lifted.clone()), lifted);
+    }
+
+    #[test]
+    fn impossible_moves() {
+        let raw = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 3 from 2 to 1\n";
+        // parsing turns the last move away, so the simulator gets it separately
+        let (valid, _) = raw.rsplit_once("move").unwrap();
+        let input = Day05::parse(valid).unwrap();
+        let mut moves = input.moves().to_vec();
+        moves.push(Move {
+            count: 3,
+            from: 1,
+            to: 0,
+            line: 8,
+        });
+        let mut simulator = Simulator::new(CrateMover9000, input.stacks());
+        let e = simulator.run(&moves).unwrap_err();
+        assert_eq!(
+            e,
+            MoveError::NotEnoughCrates {
+                line: 8,
+                stack: 2,
+                wanted: 3,
+                there: 2
+            }
+        );
+        assert_eq!(
+            e.to_string(),
+            "line 8: can't move 3 crates off stack 2, which only has 2"
+        );
+        // the move that failed didn't touch anything
+        assert_eq!(simulator.tops(), " CZ");
+
+        let e = Day05::parse(raw).unwrap_err();
+        assert_eq!((e.line, e.text.as_str()), (8, "move 3 from 2 to 1"));
+
+        let e = Day05::parse("[A]\n 1 \n\nmove 1 from 1 to 1\nmove 1 from 1 to 4\n").unwrap_err();
+        assert_eq!(e.line, 5);
+        assert_eq!(e.message, "there's no stack 4");
+    }
+
+    #[test]
+    fn history() {
+        let input = get_raw_input();
+        let input = Day05::parse(&input).unwrap();
+        let mut simulator = Simulator::recording(CrateMover9001, input.stacks());
+        simulator.run(input.moves()).unwrap();
+
+        let history = simulator.history();
+        assert_eq!(history.len(), 5);
+        assert_eq!(history[0].after, None);
+        assert_eq!(history[0].stacks, input.stacks());
+        assert_eq!(history[1].after.as_ref(), input.moves().first());
+        assert_eq!(
+            history[1].stacks,
+            [vec!["Z", "N", "D"], vec!["M", "C"], vec!["P"]]
+        );
+        assert_eq!(history[4].stacks, simulator.stacks());
+
+        // without recording, there's nothing kept
+        assert!(Simulator::new(CrateMover9001, input.stacks())
+            .history()
+            .is_empty());
+
+        let mut replay = Replay::new(simulator.into_history());
+        let start = replay.draw();
+        assert!(start.starts_with("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n"));
+        assert!(replay.step());
+        assert!(replay.draw().ends_with("move 1 from 2 to 1\n"));
+        while replay.step() {}
+        assert!(replay.draw().starts_with("        [D]\n        [N]\n"));
+        replay.restart();
+        assert_eq!(replay.draw(), start);
+    }
+}
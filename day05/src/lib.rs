@@ -0,0 +1,372 @@
+//! Day 5: Supply Stacks
+//!
+//! Stacks of crates and the moves a crane makes on them, all kept in an [Input]. Part 1 moves crates
+//! one at a time, part 2 several at once, and [crane] has those cranes and others to simulate the
+//! moves with.
+
+#![warn(missing_docs)]
+
+use std::fmt::Display;
+
+use common::animate::Playback;
+use common::error::ParseError;
+use common::nom::{blank_line, parse_complete, IResult};
+use common::{Part, Solution};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alpha0, char, newline, not_line_ending, u32 as nom_u32};
+use nom::multi::{many0, separated_list0};
+use nom::sequence::{delimited, separated_pair, terminated};
+use nom::{
+    combinator::{map, map_opt},
+    error::context,
+    sequence::{preceded, tuple},
+};
+use rand::{rngs::StdRng, Rng};
+
+use crane::{Crane, CrateMover9000, CrateMover9001, MoveError, Replay, Simulator};
+
+pub mod crane;
+
+/// Reads the drawing of the stacks and the list of moves under it
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input<'a> = Input<'a>;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Input::parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem(input, CrateMover9000)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem(input, CrateMover9001)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+
+    fn animate<'a>(input: &'a Self::Input<'_>, part: Part) -> Option<Box<dyn Playback + 'a>> {
+        Some(match part {
+            Part::One => animate(input, CrateMover9000),
+            Part::Two => animate(input, CrateMover9001),
+        })
+    }
+}
+
+/// A stack of crates, bottom first
+pub type Stack<'a> = Vec<&'a str>;
+
+/// Moving crates from the top of one stack to another. Two moves are equal when they move the same
+/// crates, whichever lines they're on.
+#[derive(Clone, Debug)]
+pub struct Move {
+    /// How many crates
+    pub count: usize,
+    /// The stack they come from, counting from 0 so one less than the input's number
+    pub from: usize,
+    /// The stack they go to, counting from 0 so one less than the input's number
+    pub to: usize,
+    /// The line of the input it's on
+    pub line: usize,
+}
+
+impl PartialEq for Move {
+    fn eq(&self, other: &Self) -> bool {
+        (self.count, self.from, self.to) == (other.count, other.from, other.to)
+    }
+}
+
+impl Eq for Move {}
+
+impl Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.count,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+impl Move {
+    fn parse(s: &str) -> IResult<&str, Move> {
+        context(
+            "move",
+            map_opt(
+                tuple((
+                    preceded(tag("move "), nom_u32),
+                    preceded(tag(" from "), nom_u32),
+                    preceded(tag(" to "), nom_u32),
+                )),
+                // stacks are numbered from 1
+                |(count, from, to)| {
+                    Some(Move {
+                        count: count as usize,
+                        from: from.checked_sub(1)? as usize,
+                        to: to.checked_sub(1)? as usize,
+                        line: 0,
+                    })
+                },
+            ),
+        )(s)
+    }
+}
+
+/// The starting stacks of crates, bottom first, and the moves to make on them
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input<'a> {
+    stacks: Vec<Stack<'a>>,
+    moves: Vec<Move>,
+}
+
+impl Input<'_> {
+    fn parse_crate(s: &str) -> IResult<&str, Option<&str>> {
+        alt((
+            map(delimited(char('['), alpha0, char(']')), Some),
+            map(tag("   "), |_| None),
+        ))(s)
+    }
+
+    fn parse_row(s: &str) -> IResult<&str, Vec<Option<&str>>> {
+        separated_list0(tag(" "), Input::parse_crate)(s)
+    }
+
+    fn invert_stacks(rows: Vec<Vec<Option<&str>>>) -> Vec<Stack<'_>> {
+        // rows might stop short if there's nothing more on the end of them
+        let stack_count = rows.iter().map(Vec::len).max().unwrap_or(0);
+        (0..stack_count)
+            .map(|n| {
+                rows.iter()
+                    .rev()
+                    .filter_map(|row| row.get(n).copied().flatten())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn parse_stacks(s: &str) -> IResult<&str, Vec<Stack<'_>>> {
+        map(
+            many0(terminated(Input::parse_row, newline)),
+            Input::invert_stacks,
+        )(s)
+    }
+
+    fn parse(raw: &str) -> Result<Input<'_>, ParseError> {
+        let mut input = parse_complete(
+            raw,
+            map(
+                separated_pair(
+                    context("stacks", Input::parse_stacks),
+                    terminated(not_line_ending, blank_line),
+                    separated_list0(newline, Move::parse),
+                ),
+                |(stacks, moves)| Input { stacks, moves },
+            ),
+        )?;
+
+        // the moves are the only lines that start like one
+        let lines = raw
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| line.starts_with("move "));
+        for (m, (idx, _)) in input.moves.iter_mut().zip(lines) {
+            m.line = idx + 1;
+        }
+        input.check_moves().map_err(|e| e.into_parse_error(raw))?;
+        Ok(input)
+    }
+
+    /// Whether every move can be made, which only depends on how tall the stacks are, not on the
+    /// crane or which crates are where
+    fn check_moves(&self) -> Result<(), MoveError> {
+        let mut heights: Vec<usize> = self.stacks.iter().map(Vec::len).collect();
+        for m in &self.moves {
+            for stack in [m.from, m.to] {
+                if stack >= heights.len() {
+                    return Err(MoveError::NoSuchStack {
+                        line: m.line,
+                        stack: stack + 1,
+                    });
+                }
+            }
+            if m.count > heights[m.from] {
+                return Err(MoveError::NotEnoughCrates {
+                    line: m.line,
+                    stack: m.from + 1,
+                    wanted: m.count,
+                    there: heights[m.from],
+                });
+            }
+            heights[m.from] -= m.count;
+            heights[m.to] += m.count;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Input<'a> {
+    /// The stacks before any moves
+    pub fn stacks(&self) -> &[Stack<'a>] {
+        &self.stacks
+    }
+
+    /// The moves to make, in order
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+}
+
+/// Nine stacks of crates and `size` moves between them. No move ever takes the last crate off a
+/// stack, so there's always a crate on top of each one at the end.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    const STACKS: usize = 9;
+
+    // with at least one stack of two, there's always a stack that can spare a crate
+    let mut heights: Vec<usize> = (0..STACKS).map(|_| rng.gen_range(1..=8)).collect();
+    heights[0] = heights[0].max(2);
+    let crates: Vec<Vec<char>> = heights
+        .iter()
+        .map(|&h| (0..h).map(|_| rng.gen_range('A'..='Z')).collect())
+        .collect();
+
+    let tallest = *heights.iter().max().unwrap();
+    let mut input: String = (0..tallest)
+        .rev()
+        .map(|level| {
+            let row: Vec<String> = crates
+                .iter()
+                .map(|stack| {
+                    stack
+                        .get(level)
+                        .map_or("   ".to_owned(), |c| format!("[{c}]"))
+                })
+                .collect();
+            row.join(" ") + "\n"
+        })
+        .collect();
+    let numbers: Vec<String> = (1..=STACKS).map(|n| format!(" {n} ")).collect();
+    input += &(numbers.join(" ") + "\n\n");
+
+    for _ in 0..size {
+        let from = loop {
+            let from = rng.gen_range(0..STACKS);
+            if heights[from] > 1 {
+                break from;
+            }
+        };
+        let to = (from + rng.gen_range(1..STACKS)) % STACKS;
+        let count = rng.gen_range(1..heights[from]);
+        heights[from] -= count;
+        heights[to] += count;
+        input += &format!("move {count} from {} to {}\n", from + 1, to + 1);
+    }
+    input
+}
+
+/// The stacks drawn the same way as the puzzle draws them, and then the moves
+fn print(input: &Input) -> String {
+    let mut text = draw(&input.stacks) + "\n";
+    for m in &input.moves {
+        text += &format!("{m}\n");
+    }
+    text
+}
+
+/// Stacks drawn the way the puzzle draws them, with the numbers underneath
+fn draw(stacks: &[Stack]) -> String {
+    // an empty row keeps stacks that are all empty from disappearing altogether
+    let tallest = stacks.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    let mut text = String::new();
+    if !stacks.is_empty() {
+        for level in (0..tallest).rev() {
+            let row: Vec<String> = stacks
+                .iter()
+                .map(|stack| {
+                    stack
+                        .get(level)
+                        .map_or("   ".to_owned(), |c| format!("[{c}]"))
+                })
+                .collect();
+            text += &(row.join(" ") + "\n");
+        }
+    }
+    let numbers: Vec<String> = (1..=stacks.len()).map(|n| format!(" {n} ")).collect();
+    text + &numbers.join(" ") + "\n"
+}
+
+fn problem(input: &Input, crane: impl Crane) -> String {
+    let mut simulator = Simulator::new(crane, &input.stacks);
+    simulator
+        .run(&input.moves)
+        .expect("moves are checked when they're parsed");
+    simulator.tops()
+}
+
+/// Replay every move, one at a time
+fn animate<'a>(input: &Input<'a>, crane: impl Crane) -> Box<dyn Playback + 'a> {
+    let mut simulator = Simulator::recording(crane, &input.stacks);
+    simulator
+        .run(&input.moves)
+        .expect("moves are checked when they're parsed");
+    Box::new(Replay::new(simulator.into_history()))
+}
+
+#[cfg(test)]
+mod test {
+    use common::Solution;
+
+    use crate::{Day05, Input, Move};
+
+    common::examples!(Day05);
+    common::properties!(Day05, [0, 1, 50]);
+
+    #[test]
+    fn testparse() {
+        assert_eq!(Input::parse_crate("[D]").unwrap().1, Some("D"));
+        assert_eq!(Input::parse_crate("   ").unwrap().1, None);
+
+        let expected = vec![Some("Z"), Some("M"), Some("P")];
+        assert_eq!(Input::parse_row("[Z] [M] [P]").unwrap().1, expected);
+
+        let expected = vec![None, Some("D"), None];
+        assert_eq!(Input::parse_row("    [D]    ").unwrap().1, expected);
+    }
+
+    #[test]
+    fn moves_equal_on_any_line() {
+        let raw = "[A]
+ 1 
+
+move 1 from 1 to 1
+move 1 from 1 to 1
+";
+        let input = Day05::parse(raw).unwrap();
+        let [first, second] = input.moves() else {
+            panic!("two moves");
+        };
+        assert_eq!((first.line, second.line), (4, 5));
+        assert_eq!(first, second);
+        assert_ne!(
+            first,
+            &Move {
+                count: 2,
+                ..first.clone()
+            }
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Day 6: Tuning Trouble
+//!
+//! Finds the first run of distinct characters in a datastream: four for the start-of-packet
+//! marker, fourteen for the start-of-message one.
+
+#![warn(missing_docs)]
+
+use std::collections::BTreeSet;
+
+use common::{error::ParseError, nom::parse_complete, Solution};
+use nom::{character::complete::alpha1, error::context};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+/// Scans the datastream for its start-of-packet and start-of-message markers
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input<'a> = &'a str;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+fn parse(input: &str) -> Result<&str, ParseError> {
+    parse_complete(input, context("datastream", alpha1))
+}
+
+fn print(datastream: &str) -> String {
+    format!("{datastream}\n")
+}
+
+fn unique_string(count: usize, line: &str) -> u32 {
+    let all_chars = line.chars().collect::<Vec<char>>();
+    let control = all_chars.windows(count).enumerate().find(|(_, chars)| {
+        let set: BTreeSet<&char> = chars.iter().collect();
+        set.len() == count
+    });
+
+    let (idx, _sequence) = control.unwrap();
+    (idx + count) as u32
+}
+
+/// A datastream `size` letters long. Most of it only uses ten letters, so the one stretch of 14
+/// different letters somewhere in it is the only start-of-message marker.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut marker: Vec<char> = ('a'..='z').collect();
+    marker.shuffle(rng);
+    marker.truncate(14);
+
+    let size = size.max(14);
+    let at = rng.gen_range(0..=size - 14);
+    let mut stream: String = (0..at).map(|_| rng.gen_range('a'..='j')).collect();
+    stream.extend(marker);
+    stream.extend((at + 14..size).map(|_| rng.gen_range('a'..='j')));
+    stream + "\n"
+}
+
+fn problem1(datastream: &str) -> u32 {
+    unique_string(4, datastream)
+}
+
+fn problem2(datastream: &str) -> u32 {
+    unique_string(14, datastream)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day06;
+
+    common::examples!(Day06);
+    common::properties!(Day06, [1, 14, 100]);
+}
@@ -0,0 +1,317 @@
+//! Working out what to delete to make room on a disk.
+//!
+//! The puzzle's device is [Disk::PUZZLE], but any capacity and amount of free space can be asked
+//! for:
+//!
+//! ```
+//! use common::Solution;
+//! use day07::{cleanup::Disk, Day07};
+//!
+//! let session = "$ cd /\n$ ls\ndir a\ndir b\n10 c\n$ cd a\n$ ls\n40 d\n$ cd ../b\n$ ls\n30 e\n";
+//! let vfs = Day07::parse(session).unwrap();
+//!
+//! // 80 used of 100, and 50 needed free, so 30 has to go
+//! let disk = Disk { capacity: 100, needed: 50 };
+//! assert_eq!(disk.shortfall(&vfs), 30);
+//!
+//! let plan = disk.plan(&vfs).unwrap();
+//! assert_eq!(plan.paths(), ["/b"]);
+//! assert_eq!(plan.free_after, 50);
+//! ```
+
+use std::{cmp::Reverse, fmt::Display};
+
+use crate::vfs::{Entry, Vfs};
+
+/// A disk of some size, and how much of it has to be left free
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Disk {
+    /// How much fits on it
+    pub capacity: u64,
+    /// How much has to be free
+    pub needed: u64,
+}
+
+impl Default for Disk {
+    fn default() -> Self {
+        Disk::PUZZLE
+    }
+}
+
+/// A directory that could be deleted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Candidate<'v> {
+    /// The directory
+    pub dir: Entry<'v>,
+    /// How much would be free after deleting it
+    pub free_after: u64,
+}
+
+/// Directories to delete, none of them inside another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan<'v> {
+    /// What to delete, biggest first
+    pub delete: Vec<Entry<'v>>,
+    /// How much deleting it all frees up
+    pub freed: u64,
+    /// How much would be free afterwards
+    pub free_after: u64,
+}
+
+impl Plan<'_> {
+    /// The path of each directory to delete
+    pub fn paths(&self) -> Vec<String> {
+        self.delete.iter().map(Entry::path).collect()
+    }
+}
+
+impl Display for Plan<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for dir in &self.delete {
+            writeln!(f, "{}\t{}", dir.size(), dir.path())?;
+        }
+        write!(f, "{} freed, {} free", self.freed, self.free_after)
+    }
+}
+
+impl Disk {
+    /// The device from the puzzle, which needs 30,000,000 free of 70,000,000 for the update
+    pub const PUZZLE: Disk = Disk {
+        capacity: 70_000_000,
+        needed: 30_000_000,
+    };
+
+    /// How much is free with everything in `vfs` on it
+    pub fn free(&self, vfs: &Vfs) -> u64 {
+        self.capacity.saturating_sub(vfs.used())
+    }
+
+    /// How much more has to be freed up
+    pub fn shortfall(&self, vfs: &Vfs) -> u64 {
+        self.needed.saturating_sub(self.free(vfs))
+    }
+
+    /// The smallest directory that frees up enough on its own, counting the root like the puzzle
+    /// does
+    pub fn smallest_to_delete<'v>(&self, vfs: &'v Vfs) -> Option<Entry<'v>> {
+        let shortfall = self.shortfall(vfs);
+        vfs.directories()
+            .filter(|dir| dir.size() >= shortfall)
+            .min_by_key(Entry::size)
+    }
+
+    /// Every directory but the root, which can't be deleted, smallest first
+    pub fn candidates<'v>(&self, vfs: &'v Vfs) -> Vec<Candidate<'v>> {
+        let free = self.free(vfs);
+        let mut candidates: Vec<Candidate> = vfs
+            .directories()
+            .filter(|dir| dir.parent().is_some())
+            .map(|dir| Candidate {
+                dir,
+                free_after: free + dir.size(),
+            })
+            .collect();
+        candidates.sort_by_key(|candidate| candidate.dir.size());
+        candidates
+    }
+
+    /// The fewest directories to delete to leave enough free, or None if even deleting
+    /// everything but the root wouldn't do
+    ///
+    /// Of the ways to free enough with that many, this looks for one that frees as little as it
+    /// can, but finding the very least is a subset sum problem, so it settles for a close one: it
+    /// takes the biggest directories it can and the smallest that finishes the job, then goes back
+    /// over each and swaps it for the smallest that still leaves enough free.
+    pub fn plan<'v>(&self, vfs: &'v Vfs) -> Option<Plan<'v>> {
+        let shortfall = self.shortfall(vfs);
+        let free = self.free(vfs);
+
+        // smallest first, and a directory after anything in it that's just as big
+        let mut dirs: Vec<Entry> = vfs
+            .directories()
+            .filter(|dir| dir.parent().is_some())
+            .collect();
+        dirs.reverse();
+        dirs.sort_by_key(Entry::size);
+
+        // the biggest directories that aren't in each other free the most for any number of them,
+        // since a directory is at least as big as anything in it, so this is how many it takes
+        let mut delete: Vec<Entry> = vec![];
+        let mut freed = 0;
+        while freed < shortfall {
+            let dir = smallest_fitting(&dirs, &delete, shortfall - freed)
+                .or_else(|| biggest_fitting(&dirs, &delete))?;
+            freed += dir.size();
+            delete.push(dir);
+        }
+
+        for idx in 0..delete.len() {
+            let dir = delete.remove(idx);
+            let rest = freed - dir.size();
+            let smaller = smallest_fitting(&dirs, &delete, shortfall.saturating_sub(rest))
+                .expect("the directory itself fits");
+            freed = rest + smaller.size();
+            delete.insert(idx, smaller);
+        }
+
+        delete.sort_by_key(|dir| Reverse(dir.size()));
+        Some(Plan {
+            delete,
+            freed,
+            free_after: free + freed,
+        })
+    }
+}
+
+/// Whether one is inside the other
+fn nested(a: &Entry, b: &Entry) -> bool {
+    a.contains(b) || b.contains(a)
+}
+
+/// The smallest of `dirs` (smallest first) that frees at least `target` and isn't nested with any
+/// of `chosen`
+fn smallest_fitting<'v>(dirs: &[Entry<'v>], chosen: &[Entry], target: u64) -> Option<Entry<'v>> {
+    let from = dirs.partition_point(|dir| dir.size() < target);
+    dirs[from..]
+        .iter()
+        .find(|dir| !chosen.iter().any(|other| nested(other, dir)))
+        .copied()
+}
+
+/// The biggest of `dirs` (smallest first) that isn't nested with any of `chosen`
+fn biggest_fitting<'v>(dirs: &[Entry<'v>], chosen: &[Entry]) -> Option<Entry<'v>> {
+    dirs.iter()
+        .rev()
+        .find(|dir| !chosen.iter().any(|other| nested(other, dir)))
+        .copied()
+}
+
+#[cfg(test)]
+mod test {
+    use common::{test::get_raw_input, Solution};
+
+    use std::cmp::Reverse;
+
+    use super::Disk;
+    use crate::Day07;
+
+    #[test]
+    fn puzzle_disk() {
+        let input = get_raw_input();
+        let vfs = Day07::parse(&input).unwrap();
+
+        let disk = Disk::default();
+        assert_eq!(disk.free(&vfs), 21618835);
+        assert_eq!(disk.shortfall(&vfs), 8381165);
+        assert_eq!(disk.smallest_to_delete(&vfs).unwrap().path(), "/d");
+
+        let candidates: Vec<(String, u64)> = disk
+            .candidates(&vfs)
+            .iter()
+            .map(|c| (c.dir.path(), c.free_after))
+            .collect();
+        assert_eq!(
+            candidates,
+            [
+                ("/a/e".to_owned(), 21619419),
+                ("/a".to_owned(), 21713688),
+                ("/d".to_owned(), 46552477),
+            ]
+        );
+
+        let plan = disk.plan(&vfs).unwrap();
+        assert_eq!(plan.paths(), ["/d"]);
+        assert_eq!((plan.freed, plan.free_after), (24933642, 46552477));
+        assert_eq!(
+            plan.to_string(),
+            "24933642\t/d\n24933642 freed, 46552477 free"
+        );
+    }
+
+    #[test]
+    fn plans() {
+        let session = "$ cd /
+$ ls
+dir a
+dir b
+dir c
+$ cd a
+$ ls
+dir x
+1 f
+$ cd x
+$ ls
+60 f
+$ cd /b
+$ ls
+50 f
+$ cd /c
+$ ls
+15 f";
+        let vfs = Day07::parse(session).unwrap();
+        assert_eq!(vfs.used(), 126);
+
+        let plan = |capacity, needed| {
+            Disk { capacity, needed }
+                .plan(&vfs)
+                .map(|plan| (plan.paths(), plan.freed))
+        };
+        // already enough free
+        assert_eq!(plan(200, 50), Some((vec![], 0)));
+        // one will do, and /b is the smallest that does
+        assert_eq!(plan(126, 50), Some((vec!["/b".to_owned()], 50)));
+        // no one directory is enough, and /a/x with /b is closer than /a with /b
+        assert_eq!(
+            plan(126, 110),
+            Some((vec!["/a/x".to_owned(), "/b".to_owned()], 110))
+        );
+        // the root can't go, so this is as much as can be freed
+        assert_eq!(
+            plan(126, 126),
+            Some((vec!["/a".to_owned(), "/b".to_owned(), "/c".to_owned()], 126))
+        );
+        assert_eq!(plan(126, 127), None);
+        assert_eq!(
+            Disk {
+                capacity: 126,
+                needed: 127
+            }
+            .smallest_to_delete(&vfs),
+            None
+        );
+    }
+
+    #[test]
+    fn many_siblings() {
+        let mut session = "$ cd /\n$ ls\n".to_owned();
+        for n in 0..200 {
+            session += &format!("dir d{n}\n");
+        }
+        let mut sizes: Vec<u64> = (0..200).map(|n| 1000 + n * 37 % 1000).collect();
+        for (n, size) in sizes.iter().enumerate() {
+            session += &format!("$ cd /d{n}\n$ ls\n{size} f\n");
+        }
+        let vfs = Day07::parse(&session).unwrap();
+
+        let used = vfs.used();
+        let disk = Disk {
+            capacity: used,
+            needed: used / 2 + 1,
+        };
+        let plan = disk.plan(&vfs).unwrap();
+        assert!(plan.free_after >= disk.needed);
+        // nothing's nested, so it takes as many as the biggest need to be
+        sizes.sort_by_key(|&size| Reverse(size));
+        let fewest = sizes
+            .iter()
+            .scan(0, |freed, size| {
+                *freed += size;
+                Some(*freed)
+            })
+            .position(|freed| freed >= disk.needed)
+            .unwrap()
+            + 1;
+        assert_eq!(plan.delete.len(), fewest);
+        assert_eq!(plan.freed, plan.delete.iter().map(|dir| dir.size()).sum());
+    }
+}
@@ -0,0 +1,572 @@
+//! Day 7: No Space Left On Device
+//!
+//! A terminal session of [Command]s exploring a filesystem, which is parsed into the [vfs::Vfs]
+//! it saw. Part 1 sums the small directories and part 2 picks the smallest one worth deleting, and
+//! the filesystem can answer plenty of other questions too. [cleanup::Disk] plans what to delete
+//! on a disk of any size.
+
+#![warn(missing_docs)]
+
+use std::{error::Error, fmt::Display};
+
+use cleanup::Disk;
+use common::{
+    error::ParseError,
+    generate::{lines, names},
+    nom::{parse_complete, IResult},
+    Solution,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    bytes::complete::take_till1,
+    character::complete::{line_ending, u64 as nom_u64},
+    combinator::{eof, map, opt, peek, verify},
+    error::context,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair, terminated},
+};
+use rand::{rngs::StdRng, Rng};
+use vfs::Vfs;
+
+pub mod cleanup;
+pub mod vfs;
+
+/// Replays the session to size up each directory
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input<'a> = Vfs;
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_session(input).map_err(|e| e.into_parse_error(input))
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input, Disk::PUZZLE)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(&input.commands())
+    }
+}
+
+/// A name and a size, for a file or a directory (where the size starts out as 0)
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Clone, Copy)]
+pub struct Data<'a> {
+    /// The name within its directory
+    pub name: &'a str,
+    /// The size in bytes
+    pub size: u64,
+}
+impl<'a> Data<'a> {
+    fn new(name: &'a str, size: u64) -> Data<'a> {
+        Data { name, size }
+    }
+}
+
+/// One line of `ls` output
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Clone, Copy)]
+pub enum Listing<'a> {
+    /// `dir NAME`
+    Directory(Data<'a>),
+    /// `SIZE NAME`
+    File(Data<'a>),
+}
+
+impl<'a> Listing<'a> {
+    fn parse(s: &str) -> IResult<&str, Vec<Listing<'_>>> {
+        separated_list1(line_ending, Listing::parse_one)(s)
+    }
+
+    fn parse_one(s: &str) -> IResult<&str, Listing<'_>> {
+        context(
+            "listing",
+            alt((
+                map(
+                    separated_pair(nom_u64, tag(" "), entry_name),
+                    |(size, name)| Listing::File(Data::new(name, size)),
+                ),
+                preceded(
+                    tag("dir "),
+                    map(entry_name, |x| Listing::Directory(Data::new(x, 0))),
+                ),
+            )),
+        )(s)
+    }
+}
+
+/// The rest of the line, which can be anything but empty
+fn rest_of_line(s: &str) -> IResult<&str, &str> {
+    take_till1(|c| c == '\r' || c == '\n')(s)
+}
+
+/// A name in a listing, which can have anything in it except a slash, and can't be `.` or `..`
+fn entry_name(s: &str) -> IResult<&str, &str> {
+    verify(rest_of_line, |name: &str| {
+        !name.contains('/') && name != "." && name != ".."
+    })(s)
+}
+
+/// The end of a command's line
+fn end_of_line(s: &str) -> IResult<&str, &str> {
+    peek(alt((line_ending, eof)))(s)
+}
+
+/// A command typed at the terminal, along with anything it printed
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord)]
+pub enum Command<'a> {
+    /// `cd /`
+    GoToRoot,
+    /// `cd ..`
+    GoUp,
+    /// `cd PATH` for any other path, like `a`, `a/b/c`, `../d` or `/a/b`
+    ChangeDir(&'a str),
+    /// `ls` and what it listed, which for an empty directory is nothing
+    List(Vec<Listing<'a>>),
+}
+
+impl<'a> Command<'a> {
+    fn parse(s: &str) -> IResult<&str, Command<'_>> {
+        context(
+            "command",
+            preceded(
+                tag("$ "),
+                alt((
+                    preceded(
+                        tag("cd "),
+                        map(rest_of_line, |x| match x {
+                            ".." => Command::GoUp,
+                            "/" => Command::GoToRoot,
+                            _ => Command::ChangeDir(x),
+                        }),
+                    ),
+                    preceded(
+                        terminated(tag("ls"), end_of_line),
+                        map(opt(preceded(line_ending, Listing::parse)), |listings| {
+                            Command::List(listings.unwrap_or_default())
+                        }),
+                    ),
+                )),
+            ),
+        )(s)
+    }
+
+    fn parse_all(s: &str) -> Result<Vec<Command<'_>>, ParseError> {
+        parse_complete(s, separated_list1(line_ending, Command::parse))
+    }
+}
+
+/// Why a terminal session couldn't be made sense of. Apart from [SessionError::Syntax], these are
+/// sessions that read fine but can't have happened on any one filesystem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionError {
+    /// It isn't a session at all
+    Syntax(ParseError),
+    /// `$ COMMAND` for anything other than `cd` and `ls`
+    UnknownCommand {
+        /// The line it was typed on
+        line: usize,
+        /// What was typed instead
+        command: String,
+    },
+    /// `cd ..` from the root, which has nothing above it
+    AboveRoot {
+        /// The line of the `cd`
+        line: usize,
+    },
+    /// A `cd` into a directory its parent's listing doesn't have
+    NoSuchDirectory {
+        /// The line of the `cd`
+        line: usize,
+        /// Where the directory would have been
+        path: String,
+    },
+    /// A `cd` into a file
+    NotADirectory {
+        /// The line of the `cd`
+        line: usize,
+        /// The file
+        path: String,
+    },
+    /// A file listed again with a different size
+    SizeChanged {
+        /// The line it was listed again on
+        line: usize,
+        /// The file
+        path: String,
+        /// The size it was listed with first
+        was: u64,
+        /// The size it was listed with this time
+        now: u64,
+    },
+    /// Something listed as a file one time and a directory another
+    KindChanged {
+        /// The line it was listed again on
+        line: usize,
+        /// What was listed
+        path: String,
+    },
+    /// Something a directory had in it before that a later `ls` of it doesn't have
+    Missing {
+        /// The line of the `ls`
+        line: usize,
+        /// What's missing
+        path: String,
+    },
+    /// The session couldn't be read any further
+    Io {
+        /// The line it was reading
+        line: usize,
+        /// Why not
+        message: String,
+    },
+}
+
+impl SessionError {
+    /// The line of the session it's about
+    pub fn line(&self) -> usize {
+        match self {
+            SessionError::Syntax(e) => e.line,
+            SessionError::UnknownCommand { line, .. }
+            | SessionError::AboveRoot { line }
+            | SessionError::NoSuchDirectory { line, .. }
+            | SessionError::NotADirectory { line, .. }
+            | SessionError::SizeChanged { line, .. }
+            | SessionError::KindChanged { line, .. }
+            | SessionError::Missing { line, .. }
+            | SessionError::Io { line, .. } => *line,
+        }
+    }
+
+    /// The error pointing at the start of its line in `input`, the session it came from
+    pub fn into_parse_error(self, input: &str) -> ParseError {
+        match self {
+            SessionError::Syntax(e) => e,
+            e => {
+                let problem = e.problem();
+                ParseError::on_line(input, e.line(), problem)
+            }
+        }
+    }
+
+    /// What's wrong, without the line it's on
+    fn problem(&self) -> String {
+        match self {
+            SessionError::Syntax(e) => e.message.clone(),
+            SessionError::UnknownCommand { command, .. } => {
+                format!("unknown command {command}, expected cd or ls")
+            }
+            SessionError::AboveRoot { .. } => {
+                "cd .. from the root, which has nothing above it".to_owned()
+            }
+            SessionError::NoSuchDirectory { path, .. } => {
+                format!("cd into {path}, which its directory's listing doesn't have")
+            }
+            SessionError::NotADirectory { path, .. } => format!("cd into {path}, which is a file"),
+            SessionError::SizeChanged { path, was, now, .. } => {
+                format!("{path} was listed with size {was} before, now {now}")
+            }
+            SessionError::KindChanged { path, .. } => {
+                format!("{path} was listed as both a file and a directory")
+            }
+            SessionError::Missing { path, .. } => {
+                format!("{path} was there before, but this listing doesn't have it")
+            }
+            SessionError::Io { message, .. } => format!("couldn't read the session: {message}"),
+        }
+    }
+}
+
+impl Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionError::Syntax(e) => write!(f, "{e}"),
+            e => write!(f, "line {}: {}", e.line(), e.problem()),
+        }
+    }
+}
+
+impl Error for SessionError {}
+
+impl From<ParseError> for SessionError {
+    fn from(e: ParseError) -> Self {
+        SessionError::Syntax(e)
+    }
+}
+
+/// The command typed on a line, if it's one there's no such thing as
+fn unknown_command(line: &str) -> Option<&str> {
+    line.strip_prefix("$ ")
+        .and_then(|typed| typed.split_whitespace().next())
+        .filter(|&command| command != "cd" && command != "ls")
+}
+
+/// Parse a terminal session and piece together the filesystem it explored, checking that it all
+/// fits together. A line that doesn't parse because it's a command other than `cd` or `ls` is a
+/// [SessionError::UnknownCommand].
+pub fn parse_session(input: &str) -> Result<Vfs, SessionError> {
+    let commands = Command::parse_all(input).map_err(|e| {
+        match input.lines().nth(e.line - 1).and_then(unknown_command) {
+            Some(command) => SessionError::UnknownCommand {
+                line: e.line,
+                command: command.to_owned(),
+            },
+            None => SessionError::Syntax(e),
+        }
+    })?;
+    Vfs::build(&commands)
+}
+
+/// The terminal session, command by command
+fn print(commands: &[Command]) -> String {
+    let commands: Vec<String> = commands
+        .iter()
+        .map(|command| match command {
+            Command::GoToRoot => "$ cd /".to_owned(),
+            Command::GoUp => "$ cd ..".to_owned(),
+            Command::ChangeDir(dir) => format!("$ cd {dir}"),
+            Command::List(listings) => {
+                let output: Vec<String> = std::iter::once("$ ls".to_owned())
+                    .chain(listings.iter().map(|listing| match listing {
+                        Listing::Directory(data) => format!("dir {}", data.name),
+                        Listing::File(data) => format!("{} {}", data.size, data.name),
+                    }))
+                    .collect();
+                output.join("\n")
+            }
+        })
+        .collect();
+    lines(commands)
+}
+
+/// A terminal session exploring a filesystem of `size` directories. The files add up to somewhere
+/// between 45 and 65 million, so the disk is never overfull but there's usually something to
+/// delete.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    struct Dir {
+        name: String,
+        children: Vec<usize>,
+        files: Vec<(String, u64)>,
+    }
+
+    // directory 0 is the root, and every other one hangs off one that came before it
+    let count = size.max(1);
+    let dir_names = names(rng, count, 4, "abcdefghijklmnopqrstuvwxyz");
+    let mut dirs: Vec<Dir> = dir_names
+        .into_iter()
+        .map(|name| Dir {
+            name,
+            children: vec![],
+            files: vec![],
+        })
+        .collect();
+    for idx in 1..count {
+        let parent = rng.gen_range(idx.saturating_sub(5)..idx);
+        dirs[parent].children.push(idx);
+    }
+
+    // every directory gets listed, so every one has to have something in it
+    let mut weights = vec![];
+    for (idx, dir) in dirs.iter_mut().enumerate() {
+        let files = if dir.children.is_empty() {
+            rng.gen_range(1..=5)
+        } else {
+            rng.gen_range(0..=3)
+        };
+        for (n, name) in names(rng, files, 6, "abcdefghijklmnopqrstuvwxyz")
+            .into_iter()
+            .enumerate()
+        {
+            let name = match rng.gen_range(0..3) {
+                0 => name,
+                1 => format!("{name}.txt"),
+                _ => format!("{name}.dat"),
+            };
+            dir.files.push((name, 0));
+            weights.push((idx, n, rng.gen_range(1..=1000u64)));
+        }
+    }
+
+    // share the total out between the files by weight
+    let total: u64 = rng.gen_range(45_000_000..=65_000_000);
+    let weight_sum: u64 = weights.iter().map(|(_, _, w)| w).sum();
+    for (dir, file, weight) in weights {
+        dirs[dir].files[file].1 = (total * weight / weight_sum).max(1);
+    }
+
+    fn explore(dirs: &[Dir], idx: usize, session: &mut Vec<String>) {
+        let dir = &dirs[idx];
+        session.push("$ ls".to_owned());
+        session.extend(
+            dir.children
+                .iter()
+                .map(|&c| format!("dir {}", dirs[c].name)),
+        );
+        session.extend(
+            dir.files
+                .iter()
+                .map(|(name, size)| format!("{size} {name}")),
+        );
+        for &child in &dir.children {
+            session.push(format!("$ cd {}", dirs[child].name));
+            explore(dirs, child, session);
+            session.push("$ cd ..".to_owned());
+        }
+    }
+
+    let mut session = vec!["$ cd /".to_owned()];
+    explore(&dirs, 0, &mut session);
+    lines(session)
+}
+
+fn problem1(vfs: &Vfs) -> u64 {
+    vfs.directories()
+        .map(|dir| dir.size())
+        .filter(|&size| size <= 100_000)
+        .sum()
+}
+
+fn problem2(vfs: &Vfs, disk: Disk) -> u64 {
+    disk.smallest_to_delete(vfs)
+        .expect("deleting everything frees up enough")
+        .size()
+}
+
+#[cfg(test)]
+mod test {
+    use common::Solution;
+
+    use crate::{parse_session, print, Command, Data, Day07, Listing, SessionError};
+
+    common::examples!(Day07);
+    common::properties!(Day07, [1, 10, 100]);
+
+    #[test]
+    fn ls() {
+        let ls = Command::parse(
+            r#"$ ls
+dir foo
+1234 foo.txt"#,
+        );
+        assert_eq!(
+            ls.unwrap().1,
+            Command::List(vec![
+                Listing::Directory(Data::new("foo", 0)),
+                Listing::File(Data::new("foo.txt", 1234)),
+            ])
+        );
+    }
+
+    #[test]
+    fn cd() {
+        let cd = Command::parse("$ cd ..").unwrap().1;
+        assert_eq!(cd, Command::GoUp);
+
+        let cd = Command::parse("$ cd /").unwrap().1;
+        assert_eq!(cd, Command::GoToRoot);
+
+        let cd = Command::parse("$ cd foo").unwrap().1;
+        assert_eq!(cd, Command::ChangeDir("foo"));
+
+        let cd = Command::parse("$ cd /foo/bar-baz/../qux.d").unwrap().1;
+        assert_eq!(cd, Command::ChangeDir("/foo/bar-baz/../qux.d"));
+
+        assert!(Command::parse("$ cd").is_err());
+        assert!(Command::parse("$ lsd").is_err());
+    }
+
+    #[test]
+    fn empty_ls() {
+        let commands = Command::parse_all("$ ls\n$ cd a\n$ ls\n").unwrap();
+        assert_eq!(
+            commands,
+            [
+                Command::List(vec![]),
+                Command::ChangeDir("a"),
+                Command::List(vec![])
+            ]
+        );
+        assert_eq!(print(&commands), "$ ls\n$ cd a\n$ ls\n");
+    }
+
+    #[test]
+    fn errors() {
+        let input = "$ cd /\n$ ls\ndir a\n$ rm -rf a\n";
+        assert_eq!(
+            parse_session(input),
+            Err(SessionError::UnknownCommand {
+                line: 4,
+                command: "rm".to_owned()
+            })
+        );
+        let e = Day07::parse(input).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (4, 1, "$ rm -rf a"));
+        assert_eq!(e.message, "unknown command rm, expected cd or ls");
+
+        let input = "$ cd /\n$ ls\n1 a\n$ ls\n2 a\n";
+        let e = parse_session(input).unwrap_err();
+        assert_eq!(e.line(), 5);
+        assert_eq!(
+            e.to_string(),
+            "line 5: /a was listed with size 1 before, now 2"
+        );
+        assert_eq!(Day07::parse(input).unwrap_err().text, "2 a");
+
+        assert!(matches!(
+            parse_session("$ ls\ndir a/b\n"),
+            Err(SessionError::Syntax(_))
+        ));
+    }
+
+    #[test]
+    fn listing() {
+        let listing = Listing::parse("dir foo").unwrap().1;
+        assert_eq!(listing, vec![Listing::Directory(Data::new("foo", 0))]);
+
+        let listing = Listing::parse("dir .config\n12 a-b c.tar.gz").unwrap().1;
+        assert_eq!(
+            listing,
+            vec![
+                Listing::Directory(Data::new(".config", 0)),
+                Listing::File(Data::new("a-b c.tar.gz", 12)),
+            ]
+        );
+        assert!(Listing::parse("dir ..").is_err());
+
+        // bigger than a u32 holds
+        let listing = Listing::parse("8589934592 disk.img").unwrap().1;
+        assert_eq!(
+            listing,
+            vec![Listing::File(Data::new("disk.img", 8_589_934_592))]
+        );
+
+        let listing = Listing::parse(
+            r#"1234 foo.txt
+dir foo
+5678 bar.txt"#,
+        )
+        .unwrap()
+        .1;
+        assert_eq!(
+            listing,
+            vec![
+                Listing::File(Data::new("foo.txt", 1234)),
+                Listing::Directory(Data::new("foo", 0)),
+                Listing::File(Data::new("bar.txt", 5678))
+            ]
+        );
+    }
+}
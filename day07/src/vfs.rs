@@ -0,0 +1,939 @@
+//! An in-memory filesystem, pieced together from what a terminal session saw of it.
+//!
+//! Everything in it is looked at through an [Entry], which knows its name, its absolute path and
+//! its size (everything underneath it, for a directory):
+//!
+//! ```
+//! use common::Solution;
+//! use day07::Day07;
+//!
+//! let session = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n$ cd a\n$ ls\n29116 f\n";
+//! let vfs = Day07::parse(session).unwrap();
+//!
+//! assert_eq!(vfs.lookup("/a/f").unwrap().size(), 29116);
+//! assert_eq!(vfs.root().size(), 14848514 + 29116);
+//!
+//! let big: Vec<String> = vfs.find(|e| e.is_file() && e.size() > 1_000_000).map(|e| e.path()).collect();
+//! assert_eq!(big, ["/b.txt"]);
+//! ```
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+    io::BufRead,
+};
+
+use common::{error::ParseError, nom::parse_complete};
+use nom::{branch::alt, combinator::map};
+
+use crate::{unknown_command, Command, Data, Listing, SessionError};
+
+/// Where a file or directory is kept in its [Vfs]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    File,
+    Directory {
+        children: BTreeMap<String, NodeId>,
+        /// Whether it's been listed, so everything in it is known
+        listed: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node {
+    name: String,
+    parent: Option<NodeId>,
+    kind: Kind,
+    /// A file's own size, or everything under a directory
+    size: u64,
+}
+
+/// A tree of directories and files, which owns all their names
+#[derive(Debug, Clone)]
+pub struct Vfs {
+    nodes: Vec<Node>,
+}
+
+/// Two filesystems are the same if they have the same files and directories with the same sizes,
+/// and the same directories have been listed, in whatever order they were pieced together
+impl PartialEq for Vfs {
+    fn eq(&self, other: &Self) -> bool {
+        let shape = |entry: Entry| (entry.path(), entry.size(), entry.listed());
+        self.walk().map(shape).eq(other.walk().map(shape))
+    }
+}
+
+impl Eq for Vfs {}
+
+impl Default for Vfs {
+    fn default() -> Self {
+        Vfs {
+            nodes: vec![Node {
+                name: "/".to_owned(),
+                parent: None,
+                kind: Kind::Directory {
+                    children: BTreeMap::new(),
+                    listed: false,
+                },
+                size: 0,
+            }],
+        }
+    }
+}
+
+impl Vfs {
+    const ROOT: NodeId = NodeId(0);
+
+    /// Replay a terminal session, checking as it goes that everything it saw could be the same
+    /// filesystem. Changing into a directory that its parent's listing doesn't have is an error,
+    /// but if the parent was never listed it's taken on trust. Line numbers count from the start
+    /// of the session, the way [Command]s are printed.
+    pub fn build(commands: &[Command]) -> Result<Vfs, SessionError> {
+        let mut builder = Builder::new();
+        for command in commands {
+            builder.command(command)?;
+        }
+        builder.finish()
+    }
+
+    /// Replay a terminal session a line at a time as it's read, like [Vfs::build] does, so only
+    /// the filesystem has to fit in memory and not the whole session
+    pub fn read(mut reader: impl BufRead) -> Result<Vfs, SessionError> {
+        let mut builder = Builder::new();
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line).map_err(|e| SessionError::Io {
+                line: builder.lines() + 1,
+                message: e.to_string(),
+            })?;
+            if read == 0 {
+                break;
+            }
+            builder.push_line(line.trim_end_matches(['\r', '\n']))?;
+        }
+        builder.finish()
+    }
+
+    /// A session that pieces together this same filesystem: from the root, each directory is
+    /// listed if it was listed before, and then each directory in it is visited in turn
+    pub fn commands(&self) -> Vec<Command<'_>> {
+        fn visit<'v>(dir: Entry<'v>, commands: &mut Vec<Command<'v>>) {
+            if dir.listed() == Some(true) {
+                let listing = dir.children().map(|child| {
+                    if child.is_dir() {
+                        Listing::Directory(Data::new(child.name(), 0))
+                    } else {
+                        Listing::File(Data::new(child.name(), child.size()))
+                    }
+                });
+                commands.push(Command::List(listing.collect()));
+            }
+            for child in dir.children().filter(Entry::is_dir) {
+                commands.push(Command::ChangeDir(child.name()));
+                visit(child, commands);
+                commands.push(Command::GoUp);
+            }
+        }
+
+        let mut commands = vec![Command::GoToRoot];
+        visit(self.root(), &mut commands);
+        commands
+    }
+
+    fn children(&self, dir: NodeId) -> &BTreeMap<String, NodeId> {
+        match &self.nodes[dir.0].kind {
+            Kind::Directory { children, .. } => children,
+            Kind::File => unreachable!("only directories are ever the current directory"),
+        }
+    }
+
+    fn child_path(&self, dir: NodeId, name: &str) -> String {
+        match self.get(dir).path().as_str() {
+            "/" => format!("/{name}"),
+            path => format!("{path}/{name}"),
+        }
+    }
+
+    /// Where `cd path` from `from` ends up
+    fn change_dir(
+        &mut self,
+        from: NodeId,
+        path: &str,
+        line: usize,
+    ) -> Result<NodeId, SessionError> {
+        let mut current = if path.starts_with('/') {
+            Vfs::ROOT
+        } else {
+            from
+        };
+        for name in path.split('/') {
+            current = match name {
+                "" | "." => current,
+                ".." => self.nodes[current.0]
+                    .parent
+                    .ok_or(SessionError::AboveRoot { line })?,
+                name => match self.children(current).get(name) {
+                    Some(&child) if self.get(child).is_dir() => child,
+                    Some(&child) => {
+                        return Err(SessionError::NotADirectory {
+                            line,
+                            path: self.get(child).path(),
+                        })
+                    }
+                    None if matches!(
+                        self.nodes[current.0].kind,
+                        Kind::Directory { listed: true, .. }
+                    ) =>
+                    {
+                        return Err(SessionError::NoSuchDirectory {
+                            line,
+                            path: self.child_path(current, name),
+                        })
+                    }
+                    None => self.add(current, name, None),
+                },
+            };
+        }
+        Ok(current)
+    }
+
+    /// Add a file of `file_size` or else a directory to `parent`, which doesn't have anything by
+    /// that name yet. A file's size goes straight onto every directory it's in, so sizes are
+    /// right at any point.
+    fn add(&mut self, parent: NodeId, name: &str, file_size: Option<u64>) -> NodeId {
+        let next = NodeId(self.nodes.len());
+        if let Kind::Directory { children, .. } = &mut self.nodes[parent.0].kind {
+            children.insert(name.to_owned(), next);
+        }
+
+        let size = file_size.unwrap_or_default();
+        let mut ancestor = Some(parent);
+        while let Some(dir) = ancestor {
+            self.nodes[dir.0].size += size;
+            ancestor = self.nodes[dir.0].parent;
+        }
+
+        self.nodes.push(Node {
+            name: name.to_owned(),
+            parent: Some(parent),
+            kind: match file_size {
+                Some(_) => Kind::File,
+                None => Kind::Directory {
+                    children: BTreeMap::new(),
+                    listed: false,
+                },
+            },
+            size,
+        });
+        next
+    }
+
+    /// The top directory, `/`
+    pub fn root(&self) -> Entry<'_> {
+        self.get(Vfs::ROOT)
+    }
+
+    /// The file or directory kept at `id`
+    pub fn get(&self, id: NodeId) -> Entry<'_> {
+        Entry { vfs: self, id }
+    }
+
+    /// Whatever is at an absolute path like `/a/e/i`. `.` and `..` work like they would in a
+    /// shell, and so do repeated or trailing slashes.
+    pub fn lookup(&self, path: &str) -> Option<Entry<'_>> {
+        let relative = path.strip_prefix('/')?;
+
+        let mut current = self.root();
+        for part in relative.split('/') {
+            current = match part {
+                "" | "." => current,
+                ".." => current.parent().unwrap_or(current),
+                name => current.child(name)?,
+            };
+        }
+        Some(current)
+    }
+
+    /// Every file and directory, each directory before what's in it, and everything in a
+    /// directory in order of name
+    pub fn walk(&self) -> impl Iterator<Item = Entry<'_>> {
+        let mut stack = vec![self.root()];
+        std::iter::from_fn(move || {
+            let entry = stack.pop()?;
+            let mut children: Vec<Entry> = entry.children().collect();
+            children.reverse();
+            stack.extend(children);
+            Some(entry)
+        })
+    }
+
+    /// Every file and directory that matches, in the order of [Vfs::walk]
+    pub fn find<'v, P>(&'v self, predicate: P) -> impl Iterator<Item = Entry<'v>>
+    where
+        P: FnMut(&Entry<'v>) -> bool + 'v,
+    {
+        self.walk().filter(predicate)
+    }
+
+    /// Every directory
+    pub fn directories(&self) -> impl Iterator<Item = Entry<'_>> {
+        self.find(Entry::is_dir)
+    }
+
+    /// The space everything takes up
+    pub fn used(&self) -> u64 {
+        self.root().size()
+    }
+
+    /// A report like `du -h`: every directory's total size and its path, each directory after
+    /// everything in it
+    pub fn du(&self) -> String {
+        fn visit(entry: Entry, report: &mut String) {
+            for child in entry.children().filter(Entry::is_dir) {
+                visit(child, report);
+            }
+            report.push_str(&format!("{}\t{}\n", human_size(entry.size()), entry.path()));
+        }
+
+        let mut report = String::new();
+        visit(self.root(), &mut report);
+        report
+    }
+
+    /// Everything drawn as a tree, like `tree` does, with each file's size and each directory's
+    /// total
+    pub fn tree(&self) -> String {
+        fn visit(entry: Entry, prefix: &str, drawing: &mut String) {
+            let children: Vec<Entry> = entry.children().collect();
+            for (n, child) in children.iter().enumerate() {
+                let last = n + 1 == children.len();
+                let (branch, indent) = if last {
+                    ("└── ", "    ")
+                } else {
+                    ("├── ", "│   ")
+                };
+                let slash = if child.is_dir() { "/" } else { "" };
+                drawing.push_str(&format!(
+                    "{prefix}{branch}{}{slash} ({})\n",
+                    child.name(),
+                    child.size()
+                ));
+                visit(*child, &format!("{prefix}{indent}"), drawing);
+            }
+        }
+
+        let mut drawing = format!("/ ({})\n", self.used());
+        visit(self.root(), "", &mut drawing);
+        drawing
+    }
+}
+
+/// The `ls` whose output is being read
+#[derive(Debug, Clone)]
+struct Ls {
+    dir: NodeId,
+    /// The line the `ls` was on
+    line: usize,
+    /// Everything it's listed so far
+    names: BTreeSet<String>,
+}
+
+/// Builds a [Vfs] a command or a line at a time, checking it the way [Vfs::build] does. What's
+/// been seen so far can be looked at in between.
+///
+/// ```
+/// use day07::vfs::Builder;
+///
+/// let mut builder = Builder::new();
+/// for line in ["$ cd /", "$ ls", "dir a", "10 b"] {
+///     builder.push_line(line).unwrap();
+/// }
+/// assert_eq!(builder.vfs().used(), 10);
+///
+/// builder.push_line("$ cd a").unwrap();
+/// assert_eq!(builder.cwd().path(), "/a");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Builder {
+    vfs: Vfs,
+    current: NodeId,
+    /// How many lines have been handled
+    line: usize,
+    listing: Option<Ls>,
+    /// The first of the blank lines since the last one that wasn't, which are only allowed at
+    /// the end
+    blank: Option<usize>,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Builder {
+            vfs: Vfs::default(),
+            current: Vfs::ROOT,
+            line: 0,
+            listing: None,
+            blank: None,
+        }
+    }
+}
+
+impl Builder {
+    /// A builder that hasn't seen anything yet
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// The filesystem as far as it's known so far
+    pub fn vfs(&self) -> &Vfs {
+        &self.vfs
+    }
+
+    /// The directory the session is in
+    pub fn cwd(&self) -> Entry<'_> {
+        self.vfs.get(self.current)
+    }
+
+    /// How many lines of the session have been handled
+    pub fn lines(&self) -> usize {
+        self.line
+    }
+
+    /// Handle a command parsed from the session, along with anything it printed
+    pub fn command(&mut self, command: &Command) -> Result<(), SessionError> {
+        self.line += 1;
+        self.run(command)?;
+        if let Command::List(listings) = command {
+            for listing in listings {
+                self.line += 1;
+                self.entry(listing)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle the next line of the session, without its line ending
+    pub fn push_line(&mut self, text: &str) -> Result<(), SessionError> {
+        self.line += 1;
+        if text.trim().is_empty() {
+            self.blank.get_or_insert(self.line);
+            return Ok(());
+        }
+        if let Some(blank) = self.blank {
+            let e = ParseError::at("", "", "expected a command or ls output");
+            return Err(SessionError::Syntax(e.offset_lines(blank - 1)));
+        }
+        if let Some(command) = unknown_command(text) {
+            return Err(SessionError::UnknownCommand {
+                line: self.line,
+                command: command.to_owned(),
+            });
+        }
+
+        let line = parse_complete(
+            text,
+            alt((
+                map(Command::parse, Line::Command),
+                map(Listing::parse_one, Line::Listing),
+            )),
+        )
+        .map_err(|e| SessionError::Syntax(e.offset_lines(self.line - 1)))?;
+        match line {
+            Line::Command(command) => self.run(&command),
+            Line::Listing(_) if self.listing.is_none() => {
+                let e = ParseError::at(text, text, "expected a command, since there's no ls");
+                Err(SessionError::Syntax(e.offset_lines(self.line - 1)))
+            }
+            Line::Listing(listing) => self.entry(&listing),
+        }
+    }
+
+    /// Everything that's been seen, once the session is over
+    pub fn finish(mut self) -> Result<Vfs, SessionError> {
+        self.end_listing()?;
+        Ok(self.vfs)
+    }
+
+    /// Start on a command on the current line, leaving what it printed for [Builder::entry]
+    fn run(&mut self, command: &Command) -> Result<(), SessionError> {
+        self.end_listing()?;
+        match command {
+            Command::GoToRoot => self.current = Vfs::ROOT,
+            Command::GoUp => self.current = self.vfs.change_dir(self.current, "..", self.line)?,
+            Command::ChangeDir(path) => {
+                self.current = self.vfs.change_dir(self.current, path, self.line)?;
+            }
+            Command::List(_) => {
+                self.listing = Some(Ls {
+                    dir: self.current,
+                    line: self.line,
+                    names: BTreeSet::new(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a line of `ls` output. Anything listed before has to be listed the same way again.
+    fn entry(&mut self, listing: &Listing) -> Result<(), SessionError> {
+        let Some(ls) = &mut self.listing else {
+            return Ok(());
+        };
+        let (name, file_size) = match listing {
+            Listing::Directory(data) => (data.name, None),
+            Listing::File(data) => (data.name, Some(data.size)),
+        };
+        let dir = ls.dir;
+        ls.names.insert(name.to_owned());
+
+        let Some(&existing) = self.vfs.children(dir).get(name) else {
+            self.vfs.add(dir, name, file_size);
+            return Ok(());
+        };
+        let existing = self.vfs.get(existing);
+        match (existing.is_file(), file_size) {
+            (true, Some(now)) if existing.size() != now => Err(SessionError::SizeChanged {
+                line: self.line,
+                path: existing.path(),
+                was: existing.size(),
+                now,
+            }),
+            (true, None) | (false, Some(_)) => Err(SessionError::KindChanged {
+                line: self.line,
+                path: existing.path(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that the `ls` that just finished had everything that was known to be there
+    fn end_listing(&mut self) -> Result<(), SessionError> {
+        let Some(ls) = self.listing.take() else {
+            return Ok(());
+        };
+        if let Some(missing) = self
+            .vfs
+            .children(ls.dir)
+            .keys()
+            .find(|name| !ls.names.contains(*name))
+        {
+            return Err(SessionError::Missing {
+                line: ls.line,
+                path: self.vfs.child_path(ls.dir, missing),
+            });
+        }
+
+        if let Kind::Directory { listed, .. } = &mut self.vfs.nodes[ls.dir.0].kind {
+            *listed = true;
+        }
+        Ok(())
+    }
+}
+
+/// A line of a session on its own
+enum Line<'a> {
+    Command(Command<'a>),
+    Listing(Listing<'a>),
+}
+
+/// A file or directory in a [Vfs]
+#[derive(Clone, Copy)]
+pub struct Entry<'v> {
+    vfs: &'v Vfs,
+    id: NodeId,
+}
+
+impl Debug for Entry<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Entry")
+            .field("path", &self.path())
+            .field("size", &self.size())
+            .finish()
+    }
+}
+
+impl PartialEq for Entry<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.vfs, other.vfs) && self.id == other.id
+    }
+}
+
+impl Eq for Entry<'_> {}
+
+impl<'v> Entry<'v> {
+    fn node(&self) -> &'v Node {
+        &self.vfs.nodes[self.id.0]
+    }
+
+    /// Where it's kept, to come back to it with [Vfs::get]
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    /// Its name in its directory, or `/` for the root
+    pub fn name(&self) -> &'v str {
+        &self.node().name
+    }
+
+    /// The absolute path to it
+    pub fn path(&self) -> String {
+        let mut names = vec![];
+        let mut current = *self;
+        while let Some(parent) = current.parent() {
+            names.push(current.name());
+            current = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    /// A file's size, or the total of every file under a directory
+    pub fn size(&self) -> u64 {
+        self.node().size
+    }
+
+    /// Whether it can have things in it
+    pub fn is_dir(&self) -> bool {
+        matches!(self.node().kind, Kind::Directory { .. })
+    }
+
+    /// Whether it has a size of its own
+    pub fn is_file(&self) -> bool {
+        !self.is_dir()
+    }
+
+    /// Whether a directory has been listed, or None for a file
+    fn listed(&self) -> Option<bool> {
+        match self.node().kind {
+            Kind::Directory { listed, .. } => Some(listed),
+            Kind::File => None,
+        }
+    }
+
+    /// Whether `other` is this or anywhere inside it
+    pub fn contains(&self, other: &Entry) -> bool {
+        let mut current = Some(*other);
+        while let Some(entry) = current {
+            if entry.id == self.id {
+                return true;
+            }
+            current = entry.parent();
+        }
+        false
+    }
+
+    /// The directory it's in, unless it's the root
+    pub fn parent(&self) -> Option<Entry<'v>> {
+        self.node().parent.map(|id| self.vfs.get(id))
+    }
+
+    /// What's in a directory, in order of name. Files don't have anything in them.
+    pub fn children(&self) -> impl Iterator<Item = Entry<'v>> {
+        let vfs = self.vfs;
+        let children = match &self.node().kind {
+            Kind::Directory { children, .. } => Some(children.values()),
+            Kind::File => None,
+        };
+        children.into_iter().flatten().map(move |&id| vfs.get(id))
+    }
+
+    /// The file or directory in this directory with this name
+    pub fn child(&self, name: &str) -> Option<Entry<'v>> {
+        match &self.node().kind {
+            Kind::Directory { children, .. } => children.get(name).map(|&id| self.vfs.get(id)),
+            Kind::File => None,
+        }
+    }
+}
+
+/// A size the way `du -h` writes one: in bytes, or rounded up to a power of 1024 with a decimal
+/// place while it's under ten
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["K", "M", "G", "T", "P", "E"];
+    if bytes < 1024 {
+        return bytes.to_string();
+    }
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    value /= 1024.0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    let tenths = (value * 10.0).ceil() / 10.0;
+    if tenths < 10.0 {
+        format!("{tenths:.1}{}", UNITS[unit])
+    } else {
+        format!("{}{}", value.ceil(), UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use common::{test::get_raw_input, Solution};
+
+    use std::io::{self, BufReader, Read};
+
+    use common::generate::seeded;
+
+    use super::{human_size, Builder, Vfs};
+    use crate::{Command, Day07, SessionError};
+
+    #[test]
+    fn lookup() {
+        let input = get_raw_input();
+        let vfs = Day07::parse(&input).unwrap();
+
+        let i = vfs.lookup("/a/e/i").unwrap();
+        assert!(i.is_file());
+        assert_eq!(
+            (i.name(), i.size(), i.path()),
+            ("i", 584, "/a/e/i".to_owned())
+        );
+
+        assert_eq!(vfs.lookup("/a/e").unwrap().size(), 584);
+        assert_eq!(vfs.lookup("/a").unwrap().size(), 94853);
+        assert_eq!(vfs.lookup("/d").unwrap().size(), 24933642);
+        assert_eq!(vfs.lookup("/").unwrap(), vfs.root());
+        assert_eq!(vfs.lookup("//a/./e/../e/i/"), Some(i));
+        assert_eq!(vfs.lookup("/../a"), vfs.lookup("/a"));
+        assert_eq!(vfs.used(), 48381165);
+
+        assert_eq!(vfs.lookup("/a/nope"), None);
+        assert_eq!(vfs.lookup("/b.txt/x"), None);
+        assert_eq!(vfs.lookup("a"), None);
+        assert_eq!(vfs.get(i.id()), i);
+    }
+
+    #[test]
+    fn find() {
+        let input = get_raw_input();
+        let vfs = Day07::parse(&input).unwrap();
+
+        let paths = |entries: Vec<_>| -> Vec<String> {
+            entries
+                .into_iter()
+                .map(|e: super::Entry| e.path())
+                .collect()
+        };
+        assert_eq!(
+            paths(vfs.directories().collect()),
+            ["/", "/a", "/a/e", "/d"]
+        );
+        assert_eq!(
+            paths(vfs.find(|e| e.name().ends_with(".txt")).collect()),
+            ["/b.txt"]
+        );
+        assert_eq!(
+            paths(vfs.find(|e| e.is_file() && e.size() > 5_000_000).collect()),
+            ["/b.txt", "/c.dat", "/d/d.ext", "/d/d.log", "/d/k"]
+        );
+        assert_eq!(vfs.walk().count(), 14);
+    }
+
+    #[test]
+    fn reports() {
+        let input = get_raw_input();
+        let vfs = Day07::parse(&input).unwrap();
+
+        assert_eq!(vfs.du(), "584\t/a/e\n93K\t/a\n24M\t/d\n47M\t/\n");
+        assert_eq!(
+            vfs.tree(),
+            "/ (48381165)
+├── a/ (94853)
+│   ├── e/ (584)
+│   │   └── i (584)
+│   ├── f (29116)
+│   ├── g (2557)
+│   └── h.lst (62596)
+├── b.txt (14848514)
+├── c.dat (8504156)
+└── d/ (24933642)
+    ├── d.ext (5626152)
+    ├── d.log (8033020)
+    ├── j (4060174)
+    └── k (7214296)
+"
+        );
+    }
+
+    #[test]
+    fn sessions_that_wander() {
+        // directories that were never listed, whole paths, and listing twice
+        let commands = Command::parse_all(
+            "$ cd x/y\n$ ls\n10 f\n$ cd /x\n$ ls\ndir y\n$ cd ../x/./y/\n$ ls\n10 f\n$ cd /\n$ ls\ndir x",
+        )
+        .unwrap();
+        let vfs = Vfs::build(&commands).unwrap();
+        assert_eq!(vfs.walk().count(), 4);
+        assert_eq!(vfs.lookup("/x/y/f").unwrap().size(), 10);
+        assert_eq!(vfs.used(), 10);
+    }
+
+    #[test]
+    fn inconsistencies() {
+        let check = |session: &str, expected: SessionError| {
+            let commands = Command::parse_all(session).unwrap();
+            assert_eq!(Vfs::build(&commands), Err(expected), "{session}");
+        };
+
+        check("$ cd /\n$ cd ..", SessionError::AboveRoot { line: 2 });
+        check("$ cd a/../..", SessionError::AboveRoot { line: 1 });
+        check(
+            "$ ls\ndir a\n$ cd b",
+            SessionError::NoSuchDirectory {
+                line: 3,
+                path: "/b".to_owned(),
+            },
+        );
+        check(
+            "$ ls\n1 a\n$ cd /a",
+            SessionError::NotADirectory {
+                line: 3,
+                path: "/a".to_owned(),
+            },
+        );
+        check(
+            "$ cd a\n$ ls\n1 f\n2 g\n$ cd ..\n$ ls\ndir a\n$ cd a\n$ ls\n1 f\n3 g",
+            SessionError::SizeChanged {
+                line: 11,
+                path: "/a/g".to_owned(),
+                was: 2,
+                now: 3,
+            },
+        );
+        check(
+            "$ ls\n1 a\ndir a",
+            SessionError::KindChanged {
+                line: 3,
+                path: "/a".to_owned(),
+            },
+        );
+        // going into a directory makes it, so the listing has to have it
+        check(
+            "$ cd a\n$ cd /\n$ ls\ndir b",
+            SessionError::Missing {
+                line: 3,
+                path: "/a".to_owned(),
+            },
+        );
+    }
+
+    #[test]
+    fn commands() {
+        // b is only ever changed into, and a is changed into before the root is listed
+        let session = "$ cd /a\n$ ls\n5 f\n$ cd /b/c\n$ cd /\n$ ls\ndir a\ndir b\n7 g\n";
+        let vfs = Day07::parse(session).unwrap();
+        let commands = vfs.commands();
+        assert_eq!(
+            crate::print(&commands),
+            "$ cd /\n$ ls\ndir a\ndir b\n7 g\n$ cd a\n$ ls\n5 f\n$ cd ..\n$ cd b\n$ cd c\n$ cd ..\n$ cd ..\n"
+        );
+        assert_eq!(Vfs::build(&commands).unwrap(), vfs);
+
+        // the same filesystem pieced together in another order is still the same
+        let other = "$ cd /\n$ ls\n7 g\ndir b\ndir a\n$ cd b\n$ cd c\n$ cd /a\n$ ls\n5 f\n";
+        assert_eq!(Day07::parse(other).unwrap(), vfs);
+        let unlisted = "$ cd /\n$ ls\n7 g\ndir b\ndir a\n$ cd b\n$ cd c\n$ cd /a\n";
+        assert_ne!(Day07::parse(unlisted).unwrap(), vfs);
+    }
+
+    #[test]
+    fn streaming() {
+        let input = get_raw_input();
+        let built = Day07::parse(&input).unwrap();
+        assert_eq!(Vfs::read(input.as_bytes()).unwrap(), built);
+
+        let input = Day07::generate(&mut seeded(7), 500);
+        let built = Day07::parse(&input).unwrap();
+        assert_eq!(Vfs::read(input.as_bytes()).unwrap(), built);
+        let crlf = input.replace('\n', "\r\n") + "\r\n\n";
+        assert_eq!(Vfs::read(crlf.as_bytes()).unwrap(), built);
+    }
+
+    #[test]
+    fn sizes_as_it_goes() {
+        let mut builder = Builder::new();
+        let mut used = vec![];
+        for line in get_raw_input().lines() {
+            builder.push_line(line).unwrap();
+            used.push(builder.vfs().used());
+        }
+        assert_eq!(builder.lines(), 23);
+        assert_eq!(builder.cwd().path(), "/d");
+        assert_eq!(&used[..5], [0, 0, 0, 14848514, 23352670]);
+        assert_eq!(used.last(), Some(&48381165));
+        assert_eq!(builder.vfs().lookup("/a/e").unwrap().size(), 584);
+    }
+
+    #[test]
+    fn streaming_errors() {
+        let read = |session: &str| Vfs::read(session.as_bytes()).unwrap_err();
+
+        let e = read("$ cd /\n10 a\n");
+        assert!(matches!(e, SessionError::Syntax(_)), "{e}");
+        assert_eq!(e.line(), 2);
+
+        let e = read("$ ls\n\n10 a\n");
+        assert!(matches!(e, SessionError::Syntax(_)), "{e}");
+        assert_eq!(e.line(), 2);
+
+        assert_eq!(
+            read("$ ls\n$ rm -rf /\n"),
+            SessionError::UnknownCommand {
+                line: 2,
+                command: "rm".to_owned()
+            }
+        );
+        let e = read("$ ls\n$ ls -la\n");
+        assert!(matches!(e, SessionError::Syntax(_)), "{e}");
+        assert_eq!(e.line(), 2);
+        assert_eq!(
+            read("$ ls\n1 a\n$ cd /\n$ ls\n2 a"),
+            SessionError::SizeChanged {
+                line: 5,
+                path: "/a".to_owned(),
+                was: 1,
+                now: 2
+            }
+        );
+        assert_eq!(
+            read("$ cd a\n$ cd /\n$ ls\ndir b"),
+            SessionError::Missing {
+                line: 3,
+                path: "/a".to_owned()
+            }
+        );
+
+        struct Broken;
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("disk on fire"))
+            }
+        }
+        let e = Vfs::read(BufReader::new(b"$ cd /\n$ ls\n".chain(Broken))).unwrap_err();
+        assert_eq!(
+            e,
+            SessionError::Io {
+                line: 3,
+                message: "disk on fire".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn human_sizes() {
+        assert_eq!(human_size(0), "0");
+        assert_eq!(human_size(1023), "1023");
+        assert_eq!(human_size(1024), "1.0K");
+        assert_eq!(human_size(1025), "1.1K");
+        assert_eq!(human_size(10 * 1024), "10K");
+        assert_eq!(human_size(14_848_514), "15M");
+        assert_eq!(human_size(5 * 1024 * 1024 * 1024), "5.0G");
+    }
+}
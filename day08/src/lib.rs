@@ -0,0 +1,123 @@
+//! Day 8: Treetop Tree House
+//!
+//! A grid of tree heights; part 1 counts the trees visible from outside, part 2 finds the best
+//! scenic score.
+
+#![warn(missing_docs)]
+
+use common::{
+    error::ParseError,
+    generate,
+    map::{Map, MapSquare},
+    nom::{grid, parse_complete},
+    orthogonal::Orthogonal,
+    Solution,
+};
+use nom::combinator::map;
+use rand::{rngs::StdRng, Rng};
+
+/// Reads the grid of tree heights and looks along its rows and columns
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Input<'a> = Map<Tree>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+type Tree = u32;
+
+fn parse(lines: &str) -> Result<Map<Tree>, ParseError> {
+    parse_complete(lines, map(grid(|c| c.to_digit(10)), Map::new))
+}
+
+/// A `size` by `size` forest of random tree heights
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let side = size.max(1);
+    generate::grid(side, side, |_, _| rng.gen_range('0'..='9'))
+}
+
+fn print(map: &Map<Tree>) -> String {
+    generate::lines(
+        map.points
+            .iter()
+            .map(|row| row.iter().map(Tree::to_string).collect::<String>()),
+    )
+}
+
+fn problem1(map: &Map<Tree>) -> u32 {
+    map.iter().fold(0, |acc, square| {
+        let mut neighbors = map.orthogonal_neighbors(&square);
+        let tree = square.data;
+
+        // check the vertical and horizontal from this tree
+        let visible_from_north = neighbors.north.all(|h| h.data < tree);
+        let visible_from_south = neighbors.south.all(|h| h.data < tree);
+        let visible_from_west = neighbors.west.all(|h| h.data < tree);
+        let visible_from_east = neighbors.east.all(|h| h.data < tree);
+
+        let is_visible =
+            visible_from_north || visible_from_south || visible_from_east || visible_from_west;
+
+        acc + is_visible as u32
+    })
+}
+
+fn view<'a>(height: &'a Tree, neighbors: impl Iterator<Item = MapSquare<'a, Tree>>) -> u32 {
+    let mut view = 0;
+    for h in neighbors {
+        view += 1;
+        if h.data >= height {
+            break;
+        }
+    }
+
+    view
+}
+
+fn problem2(map: &Map<Tree>) -> u32 {
+    map.iter()
+        .map(|square| {
+            let tree = square.data;
+            let neighbors = map.orthogonal_neighbors(&square);
+
+            let north = view(tree, neighbors.north);
+            let south = view(tree, neighbors.south);
+            let east = view(tree, neighbors.east);
+            let west = view(tree, neighbors.west);
+
+            north * south * east * west
+        })
+        .max()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day08;
+
+    common::examples!(Day08);
+    common::properties!(Day08, [1, 5, 40]);
+}
@@ -0,0 +1,298 @@
+//! Day 9: Rope Bridge
+//!
+//! Moves of a rope's head around a grid; counts the places its tail visits with two knots for part
+//! 1 and ten for part 2.
+
+#![warn(missing_docs)]
+
+use std::fmt::Display;
+
+use common::{
+    animate::{Playback, Steps},
+    error::ParseError,
+    generate::lines,
+    nom::parse_lines,
+    point::Dir4,
+    render::{Frame, Recorder, Render},
+    sparse::{Bounds, Point, SparseGrid},
+    Part, Solution,
+};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{anychar, u32 as nom_u32},
+    combinator::map_opt,
+    error::context,
+    sequence::separated_pair,
+};
+use rand::{rngs::StdRng, Rng};
+
+/// Drags a rope of two or ten knots through the head's moves
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input<'a> = Input;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+
+    fn render(input: &Self::Input<'_>, part: Part) -> Option<Vec<Frame>> {
+        Some(match part {
+            Part::One => render(2, input),
+            Part::Two => render(10, input),
+        })
+    }
+
+    fn animate<'a>(input: &'a Self::Input<'_>, part: Part) -> Option<Box<dyn Playback + 'a>> {
+        Some(match part {
+            Part::One => animate(2, input),
+            Part::Two => animate(10, input),
+        })
+    }
+}
+
+type Step = (Dir4, u32);
+/// The head's moves, in order
+pub type Input = Vec<Step>;
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_lines(
+        input,
+        context(
+            "step",
+            separated_pair(map_opt(anychar, Dir4::from_letter), tag(" "), nom_u32),
+        ),
+    )
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Knot(Point);
+impl Knot {
+    /** Calculate the follower position according to these moves.
+
+    Given:
+     * L: the leader
+     * 1: the possible positions for problem 1
+     * 2: the possible extra position for problem 2
+
+    We want to translate them to the following
+
+    ```text
+        input        result
+        21112        .....
+        1...1        .212.
+        1.H.1  --->  .1H1.
+        1...1        .212.
+        21112        .....
+    ```
+    */
+    fn get_follower_position(&self, follower: Knot) -> Knot {
+        let Knot(leader) = *self;
+        let Knot(follower) = follower;
+
+        /* Neat, TIL about https://en.wikipedia.org/wiki/Chebyshev_distance which replaces
+        the crappy manual math I had done.
+         */
+
+        Knot(if leader.chebyshev(follower) > 1 {
+            follower + (leader - follower).map(i64::signum)
+        } else {
+            follower
+        })
+    }
+
+    fn move_dir(&self, dir: Dir4) -> Knot {
+        Knot(self.0 + dir)
+    }
+}
+
+/// `size` moves of the head, each up to 20 steps in a random direction
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    lines((0..size.max(1)).map(|_| {
+        let dir = Dir4::ALL[rng.gen_range(0..4)];
+        format!("{} {}", dir.letter(), rng.gen_range(1..=20))
+    }))
+}
+
+fn print(steps: &Input) -> String {
+    lines(
+        steps
+            .iter()
+            .map(|(dir, count)| format!("{} {count}", dir.letter())),
+    )
+}
+
+/// Every move the head makes, one square at a time
+fn moves(input: &Input) -> impl Iterator<Item = Dir4> + '_ {
+    input
+        .iter()
+        .flat_map(|&(dir, count)| std::iter::repeat_n(dir, count as usize))
+}
+
+#[derive(Clone, Copy)]
+enum Cell {
+    Visited,
+    Knot(usize),
+}
+
+#[derive(Clone)]
+struct Rope {
+    knots: Vec<Knot>,
+    /// Everywhere the tail has been
+    visited: SparseGrid<()>,
+}
+
+impl Rope {
+    fn new(knot_count: usize) -> Rope {
+        let mut visited = SparseGrid::new();
+        visited.insert(Point::default(), ());
+        Rope {
+            knots: vec![Knot(Point::default()); knot_count],
+            visited,
+        }
+    }
+
+    fn pull(&mut self, dir: Dir4) {
+        // first move the leader
+        self.knots[0] = self.knots[0].move_dir(dir);
+
+        // now move all the rest according to the one in front of them
+        for k in 1..self.knots.len() {
+            self.knots[k] = self.knots[k - 1].get_follower_position(self.knots[k]);
+        }
+
+        // only track the tail positions
+        let Knot(tail) = self.knots[self.knots.len() - 1];
+        self.visited.insert(tail, ());
+    }
+
+    /// The knots on top of everywhere the tail has been
+    fn cells(&self) -> SparseGrid<Cell> {
+        let mut grid: SparseGrid<Cell> =
+            self.visited.points().map(|p| (p, Cell::Visited)).collect();
+        // go backwards so the earlier knots get drawn on top
+        for (idx, Knot(p)) in self.knots.iter().enumerate().rev() {
+            grid.insert(*p, Cell::Knot(idx));
+        }
+        grid
+    }
+}
+
+impl Display for Rope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rendered = self.cells().render(|p, cell| match cell {
+            Some(Cell::Knot(0)) => 'H',
+            Some(Cell::Knot(n)) => char::from_digit(*n as u32, 10).unwrap_or('T'),
+            Some(Cell::Visited) => '#',
+            None if p == Point::default() => 's',
+            None => '.',
+        });
+        write!(f, "{rendered}")
+    }
+}
+
+/// A rope partway through the moves
+struct Pulling<I> {
+    rope: Rope,
+    moves: I,
+}
+
+impl<I> Display for Pulling<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.rope.fmt(f)
+    }
+}
+
+fn problem(knot_count: usize, input: &Input) -> usize {
+    let mut rope = Rope::new(knot_count);
+    moves(input).for_each(|dir| rope.pull(dir));
+    rope.visited.len()
+}
+
+fn render(knot_count: usize, input: &Input) -> Vec<Frame> {
+    let mut recorder = Recorder::default();
+    let mut rope = Rope::new(knot_count);
+    for dir in moves(input) {
+        recorder.step(|| rope.clone());
+        rope.pull(dir);
+    }
+    let snapshots = recorder.finish(rope);
+
+    // the last snapshot has everywhere the tail has been, and every knot that gets drawn has to
+    // fit too
+    let all_visited = &snapshots.last().unwrap().visited;
+    let bounds = Bounds::around(
+        snapshots
+            .iter()
+            .flat_map(|rope| rope.knots.iter().map(|k| k.0))
+            .chain(all_visited.points()),
+    )
+    .unwrap();
+
+    let tail = knot_count - 1;
+    snapshots
+        .iter()
+        .map(|rope| {
+            rope.cells().within(bounds).frame(|(p, cell)| match cell {
+                Some(Cell::Knot(0)) => [230, 60, 50],
+                Some(Cell::Knot(n)) if *n == tail => [240, 200, 60],
+                Some(Cell::Knot(_)) => [200, 120, 60],
+                Some(Cell::Visited) => [90, 90, 110],
+                None if p == Point::default() => [60, 160, 90],
+                None => [20, 20, 30],
+            })
+        })
+        .collect()
+}
+
+fn animate(knot_count: usize, input: &Input) -> Box<dyn Playback + '_> {
+    Box::new(Steps::new(
+        move || Pulling {
+            rope: Rope::new(knot_count),
+            moves: moves(input),
+        },
+        |pulling| match pulling.moves.next() {
+            Some(dir) => {
+                pulling.rope.pull(dir);
+                true
+            }
+            None => false,
+        },
+    ))
+}
+
+fn problem1(input: &Input) -> usize {
+    problem(2, input)
+}
+
+fn problem2(input: &Input) -> usize {
+    problem(10, input)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day09;
+
+    common::examples!(Day09);
+    common::properties!(Day09, [1, 50]);
+}
@@ -0,0 +1,214 @@
+//! Day 10: Cathode-Ray Tube
+//!
+//! A program of [Instruction]s for a tiny CPU; part 1 sums signal strengths, part 2 draws what
+//! the CRT shows.
+
+#![warn(missing_docs)]
+
+use std::vec;
+
+use common::{error::ParseError, generate::lines, nom::parse_lines, Solution};
+use nom::{branch::alt, bytes::complete::tag, combinator::map, error::context, sequence::preceded};
+use rand::{rngs::StdRng, Rng};
+
+/// Runs the program one cycle at a time
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input<'a> = Input;
+    type Part1 = i32;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+/// One line of the program
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// `noop`, which takes a cycle
+    NoOp,
+    /// `addx V`, which takes two cycles and then adds V to the X register
+    AddX(i32),
+}
+
+/// The program, one instruction per line
+pub type Input = Vec<Instruction>;
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_lines(
+        input,
+        context(
+            "instruction",
+            alt((
+                map(tag("noop"), |_| Instruction::NoOp),
+                map(preceded(tag("addx "), nom::character::complete::i32), |x| {
+                    Instruction::AddX(x)
+                }),
+            )),
+        ),
+    )
+}
+
+/// A program that runs for `size` cycles, up to the 240 the CRT has room for, keeping the sprite
+/// somewhere on the screen
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let cycles = size.clamp(1, 240);
+    let mut program = vec![];
+    let (mut cycle, mut x) = (0, 1);
+    while cycle < cycles {
+        if cycle + 2 <= cycles && rng.gen_bool(0.6) {
+            let to = rng.gen_range(0..40);
+            program.push(format!("addx {}", to - x));
+            x = to;
+            cycle += 2;
+        } else {
+            program.push("noop".to_owned());
+            cycle += 1;
+        }
+    }
+    lines(program)
+}
+
+fn print(program: &Input) -> String {
+    lines(program.iter().map(|instruction| match instruction {
+        Instruction::NoOp => "noop".to_owned(),
+        Instruction::AddX(x) => format!("addx {x}"),
+    }))
+}
+
+const INTERESTING: [u32; 6] = [20, 60, 100, 140, 180, 220];
+fn problem1(lines: &Input) -> i32 {
+    let mut signals = vec![];
+
+    let mut cpu = Cpu::new(lines);
+    cpu.execute(|CycleResult { cycle, register_x }| {
+        if INTERESTING.contains(&cycle) {
+            let signal_strength = (cycle as i32) * register_x;
+            signals.push(signal_strength);
+        }
+    });
+
+    signals.iter().sum()
+}
+
+#[derive(Debug)]
+struct Crt {
+    pixels: [bool; 240],
+}
+
+impl Crt {
+    fn draw(&mut self, CycleResult { cycle, register_x }: CycleResult) {
+        let current = (cycle - 1) % 40;
+
+        let on = (register_x - 1..=register_x + 1).any(|x| x == (current as i32));
+        self.pixels[(cycle - 1) as usize] = on;
+    }
+
+    fn get_message(&self) -> String {
+        let v: Vec<String> = self
+            .pixels
+            .chunks(40)
+            .map(|x| {
+                x.iter()
+                    .map(|x| match x {
+                        true => "#",
+                        false => " ",
+                    })
+                    .collect()
+            })
+            .collect();
+
+        v.join("\n")
+    }
+}
+
+fn problem2(lines: &Input) -> String {
+    let mut cpu = Cpu::new(lines);
+    let mut crt = Crt {
+        pixels: [false; 240],
+    };
+
+    cpu.execute(|result| {
+        crt.draw(result);
+    });
+
+    crt.get_message()
+}
+
+struct Cpu<'a> {
+    cycle: u32,
+    register_x: i32,
+    instructions: &'a [Instruction],
+}
+
+struct CycleResult {
+    cycle: u32,
+    register_x: i32,
+}
+
+impl<'a> Cpu<'a> {
+    fn new(instructions: &'a [Instruction]) -> Self {
+        Cpu {
+            register_x: 1,
+            cycle: 0,
+            instructions,
+        }
+    }
+
+    fn execute(&mut self, mut f: impl FnMut(CycleResult)) {
+        for i in self.instructions {
+            match i {
+                Instruction::AddX(v) => {
+                    self.cycle += 1;
+                    f(CycleResult {
+                        cycle: self.cycle,
+                        register_x: self.register_x,
+                    });
+
+                    self.cycle += 1;
+                    f(CycleResult {
+                        cycle: self.cycle,
+                        register_x: self.register_x,
+                    });
+
+                    self.register_x += v;
+                }
+                Instruction::NoOp => {
+                    self.cycle += 1;
+                    f(CycleResult {
+                        cycle: self.cycle,
+                        register_x: self.register_x,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day10;
+
+    common::examples!(Day10);
+    common::properties!(Day10, [1, 100, 240, 1000]);
+}
@@ -0,0 +1,319 @@
+//! Day 11: Monkey in the Middle
+//!
+//! [Monkey]s throwing items at each other by their worry level; both parts multiply the two
+//! busiest monkeys' counts, part 2 over many more rounds without the relief.
+
+#![warn(missing_docs)]
+
+use std::cmp::Reverse;
+
+use common::{
+    error::ParseError,
+    nom::{comma_list, parse_complete, sections, IResult},
+    Solution,
+};
+use nom::branch::alt;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{newline, u64 as nom_u64},
+    combinator::map,
+    error::context,
+    sequence::{delimited, preceded, tuple},
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+/// Plays the monkeys' rounds of keep away
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input<'a> = Input;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(&mut input.clone())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(&mut input.clone())
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+/// Every monkey, in the order they take their turns
+pub type Input = Vec<Monkey>;
+
+/// A monkey holding items, and the rules it goes by when it inspects and throws them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monkey {
+    number: u64,
+    items: Vec<u64>,
+    operation: Operation,
+    divisible_by: u64,
+    if_true: usize,
+    if_false: usize,
+    inspected: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OperationValue {
+    Constant(u64),
+    Old,
+}
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Operation {
+    Add(OperationValue),
+    Mul(OperationValue),
+}
+
+fn parse_operation(input: &str) -> IResult<&str, Operation> {
+    let value = || {
+        alt((
+            map(nom_u64, OperationValue::Constant),
+            map(tag("old"), |_| OperationValue::Old),
+        ))
+    };
+    alt((
+        map(preceded(tag("+ "), value()), Operation::Add),
+        map(preceded(tag("* "), value()), Operation::Mul),
+    ))(input)
+}
+fn parse_monkey(input: &str) -> IResult<&str, Monkey> {
+    context(
+        "monkey",
+        map(
+            tuple((
+                delimited(tag("Monkey "), nom_u64, tag(":\n")),
+                delimited(tag("  Starting items: "), comma_list(nom_u64), newline),
+                delimited(tag("  Operation: new = old "), parse_operation, newline),
+                delimited(tag("  Test: divisible by "), nom_u64, newline),
+                delimited(tag("    If true: throw to monkey "), nom_u64, newline),
+                preceded(tag("    If false: throw to monkey "), nom_u64),
+            )),
+            |(number, items, operation, divisible_by, if_true, if_false)| {
+                let items = items.to_vec();
+                Monkey {
+                    number,
+                    items,
+                    operation,
+                    divisible_by,
+                    if_true: if_true as usize,
+                    if_false: if_false as usize,
+                    inspected: 0,
+                }
+            },
+        ),
+    )(input)
+}
+fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_complete(input, sections(parse_monkey))
+}
+
+/// Between two and nine monkeys, as many as `size` asks for. Their divisors are different primes,
+/// so multiplied together they stay small enough that part 2's worry levels can be squared. Part 1
+/// doesn't keep its worry levels down that way, so monkeys are rerolled until all of part 1 fits
+/// in a u64, giving up on having a monkey that squares if that keeps failing.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    const PRIMES: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    let count = size.clamp(2, PRIMES.len());
+
+    fn worry(operation: &Operation, item: u64) -> Option<u64> {
+        match operation {
+            Operation::Add(OperationValue::Constant(x)) => item.checked_add(*x),
+            Operation::Add(OperationValue::Old) => item.checked_add(item),
+            Operation::Mul(OperationValue::Constant(x)) => item.checked_mul(*x),
+            Operation::Mul(OperationValue::Old) => item.checked_mul(item),
+        }
+    }
+
+    let fits = |monkeys: &[Monkey]| {
+        let mut items: Vec<Vec<u64>> = monkeys.iter().map(|m| m.items.clone()).collect();
+        for _ in 0..20 {
+            for (n, monkey) in monkeys.iter().enumerate() {
+                for item in std::mem::take(&mut items[n]) {
+                    let Some(item) = worry(&monkey.operation, item) else {
+                        return false;
+                    };
+                    let item = item / 3;
+                    let to = if item % monkey.divisible_by == 0 {
+                        monkey.if_true
+                    } else {
+                        monkey.if_false
+                    };
+                    items[to].push(item);
+                }
+            }
+        }
+        true
+    };
+
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let mut divisors = PRIMES;
+        divisors.shuffle(rng);
+
+        // like the real thing, only one monkey squares its items
+        let squarer = (attempts <= 100).then(|| rng.gen_range(0..count));
+        let monkeys: Vec<Monkey> = (0..count)
+            .map(|n| {
+                let operation = if Some(n) == squarer {
+                    Operation::Mul(OperationValue::Old)
+                } else if rng.gen() {
+                    Operation::Add(OperationValue::Constant(rng.gen_range(1..=8)))
+                } else {
+                    Operation::Mul(OperationValue::Constant(rng.gen_range(2..=19)))
+                };
+                Monkey {
+                    number: n as u64,
+                    items: (0..rng.gen_range(1..=6))
+                        .map(|_| rng.gen_range(50..100))
+                        .collect(),
+                    operation,
+                    divisible_by: divisors[n],
+                    // never to themselves
+                    if_true: (n + rng.gen_range(1..count)) % count,
+                    if_false: (n + rng.gen_range(1..count)) % count,
+                    inspected: 0,
+                }
+            })
+            .collect();
+
+        if fits(&monkeys) {
+            return print(&monkeys);
+        }
+    }
+}
+
+fn print(monkeys: &Input) -> String {
+    let notes: Vec<String> = monkeys
+        .iter()
+        .map(|m| {
+            let items: Vec<String> = m.items.iter().map(u64::to_string).collect();
+            let operation = match &m.operation {
+                Operation::Add(OperationValue::Constant(x)) => format!("+ {x}"),
+                Operation::Add(OperationValue::Old) => "+ old".to_owned(),
+                Operation::Mul(OperationValue::Constant(x)) => format!("* {x}"),
+                Operation::Mul(OperationValue::Old) => "* old".to_owned(),
+            };
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = old {operation}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                m.number,
+                items.join(", "),
+                m.divisible_by,
+                m.if_true,
+                m.if_false
+            )
+        })
+        .collect();
+    notes.join("\n")
+}
+
+type ThrowTo = (u64, usize);
+
+impl Monkey {
+    fn inspect_all(&mut self, worry_divisor: Option<u64>) -> Vec<ThrowTo> {
+        // figure out where all the items are going
+        let results: Vec<(u64, usize)> = self
+            .items
+            .iter()
+            .map(|item| self.inspect(item, worry_divisor))
+            .collect();
+
+        self.inspected += results.len();
+
+        // clear out this monkey's items
+        self.items.clear();
+
+        results
+    }
+    fn inspect(&self, item: &u64, worry_divisor: Option<u64>) -> ThrowTo {
+        // do the operation
+        let item = match &self.operation {
+            Operation::Add(v) => {
+                item + match v {
+                    OperationValue::Constant(x) => x,
+                    OperationValue::Old => item,
+                }
+            }
+            Operation::Mul(v) => {
+                item * match v {
+                    OperationValue::Constant(x) => x,
+                    OperationValue::Old => item,
+                }
+            }
+        };
+
+        // part 1 has us divide by 3, in part 2 we need to modulo by the LCM of the monkeys divisibility
+        let item = match worry_divisor {
+            None => item / 3,
+            Some(x) => item % x,
+        };
+
+        //
+        let result = item % self.divisible_by == 0;
+
+        let throw_to = if result { self.if_true } else { self.if_false };
+
+        (item, throw_to)
+    }
+}
+
+fn round(monkeys: &mut Input, worry_divisor: Option<u64>) {
+    for n in 0..monkeys.len() {
+        // println!("Monkey {}:", n);
+        let monkey = monkeys.get_mut(n).unwrap();
+
+        let results = monkey.inspect_all(worry_divisor);
+
+        // distribute to the other monkeys
+        for (item, throw_to) in results {
+            monkeys.get_mut(throw_to).unwrap().items.push(item);
+        }
+    }
+}
+
+fn get_monkey_business(monkeys: &mut Input) -> usize {
+    monkeys.sort_by_key(|m| Reverse(m.inspected));
+
+    monkeys[0].inspected * monkeys[1].inspected
+}
+
+fn problem1(monkeys: &mut Input) -> usize {
+    for _n in 1..=20 {
+        round(monkeys, None);
+    }
+
+    get_monkey_business(monkeys)
+}
+
+fn problem2(monkeys: &mut Input) -> usize {
+    let lcm: u64 = monkeys.iter().map(|m| m.divisible_by).product();
+
+    for _n in 1..=10000 {
+        round(monkeys, Some(lcm));
+    }
+
+    get_monkey_business(monkeys)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day11;
+
+    common::examples!(Day11);
+    common::properties!(Day11, [1, 4, 9, 20]);
+}
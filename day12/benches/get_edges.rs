@@ -0,0 +1,15 @@
+use std::fs;
+
+use common::Solution;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use day12::{get_edges, Day12};
+
+fn bench_get_edges(c: &mut Criterion) {
+    let raw = fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt")).unwrap();
+    let map = Day12::parse(&raw).unwrap();
+
+    c.bench_function("get_edges", |b| b.iter(|| get_edges(black_box(&map))));
+}
+
+criterion_group!(benches, bench_get_edges);
+criterion_main!(benches);
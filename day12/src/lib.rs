@@ -0,0 +1,212 @@
+//! Day 12: Hill Climbing Algorithm
+//!
+//! A heightmap of [Position]s; the shortest climb from the start to the end, then from any
+//! lowest square.
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+
+use common::map::Map;
+use common::search::bfs;
+use common::{
+    error::ParseError,
+    generate,
+    nom::{grid, parse_complete},
+    Solution,
+};
+use nom::combinator::map;
+use rand::{rngs::StdRng, Rng};
+
+/// Searches the heightmap breadth first
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input<'a> = Input;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+/// A square of the heightmap. Its height as a number comes from `u32::from`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Position {
+    /// `S`, at height `a`
+    Start,
+    /// `E`, at height `z`
+    End,
+    /// Any other square, with its height from `a` to `z`
+    Normal(char),
+}
+
+impl From<&Position> for u32 {
+    fn from(val: &Position) -> Self {
+        (match val {
+            Position::Start => 'a',
+            Position::End => 'z',
+            Position::Normal(c) => *c,
+        }) as u32
+    }
+}
+
+impl Position {
+    fn can_travel_to(&self, dest: &Position) -> bool {
+        let start_height: u32 = self.into();
+        let dest_height: u32 = dest.into();
+
+        dest_height <= start_height + 1
+    }
+
+    fn is_potential_start(&self) -> bool {
+        matches!(self, Position::Start | Position::Normal('a'))
+    }
+}
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_complete(
+        input,
+        map(
+            grid(|c| {
+                Some(match c {
+                    'S' => Position::Start,
+                    'E' => Position::End,
+                    c => Position::Normal(c),
+                })
+            }),
+            Map::new,
+        ),
+    )
+}
+
+/// A random heightmap `size` rows high (at least 14) and twice as wide. A staircase from the top
+/// left to the bottom right climbs slowly enough from a to z that the summit is always reachable.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let height = size.max(14);
+    let width = height * 2;
+
+    // the staircase takes one step per square, so it needs at least 25 of them to climb
+    let steps = width + height - 2;
+    let mut path = HashMap::new();
+    let (mut x, mut y) = (0, 0);
+    for step in 0..=steps {
+        let letter = match step {
+            0 => 'S',
+            _ if step == steps => 'E',
+            _ => (b'a' + (step * 25 / steps) as u8) as char,
+        };
+        path.insert((x, y), letter);
+        if y == height - 1 || (x < width - 1 && rng.gen_ratio(2, 3)) {
+            x += 1;
+        } else {
+            y += 1;
+        }
+    }
+
+    generate::grid(width, height, |x, y| {
+        path.get(&(x, y))
+            .copied()
+            .unwrap_or_else(|| rng.gen_range('a'..='z'))
+    })
+}
+
+/// The heightmap, with the start and end marked on it
+pub type Input = Map<Position>;
+
+fn print(map: &Input) -> String {
+    generate::lines(map.points.iter().map(|row| {
+        row.iter()
+            .map(|p| match p {
+                Position::Start => 'S',
+                Position::End => 'E',
+                Position::Normal(c) => *c,
+            })
+            .collect::<String>()
+    }))
+}
+
+/// Every square's reachable neighbors, indexed by grid index
+pub fn get_edges(map: &Map<Position>) -> Vec<Vec<usize>> {
+    map.iter()
+        .map(|square| {
+            square
+                .neighbors()
+                .filter(|neighbor| square.data.can_travel_to(neighbor.data))
+                .map(|neighbor| neighbor.get_grid_index())
+                .collect()
+        })
+        .collect()
+}
+
+/// The fewest steps from any of the starts to the finish
+fn shortest_path(
+    edges: &[Vec<usize>],
+    starts: impl IntoIterator<Item = usize>,
+    finish: usize,
+) -> Option<usize> {
+    bfs(starts, |&n| edges[n].iter().copied(), |&n| n == finish).map(|found| found.cost)
+}
+
+fn problem1(map: &Input) -> usize {
+    let mut start: usize = 0;
+    let mut finish: usize = 0;
+
+    // find both the start and finish squares
+    for square in map.iter() {
+        match square.data {
+            Position::Start => start = square.get_grid_index(),
+            Position::End => finish = square.get_grid_index(),
+            Position::Normal(_) => {}
+        }
+    }
+    let edges = get_edges(map);
+    shortest_path(&edges, [start], finish).unwrap()
+}
+
+fn problem2(map: &Input) -> usize {
+    // find the only finish square
+    let mut finish: usize = 0;
+    for square in map.iter() {
+        if let Position::End = square.data {
+            finish = square.get_grid_index()
+        }
+    }
+
+    let edges = get_edges(map);
+
+    // search from every potential starting location at once, so the first to reach the finish wins
+    let starts = map
+        .iter()
+        .filter(|s| s.data.is_potential_start())
+        .map(|s| s.get_grid_index());
+
+    shortest_path(&edges, starts, finish).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day12;
+
+    common::examples!(Day12);
+    common::properties!(Day12, [1, 14, 40]);
+}
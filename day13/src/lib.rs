@@ -0,0 +1,273 @@
+//! Day 13: Distress Signal
+//!
+//! Pairs of nested [Packet]s, which are ordered the way the puzzle compares them. Part 1 finds
+//! the pairs already in order, part 2 sorts everything around two divider packets.
+
+#![warn(missing_docs)]
+
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use common::{
+    error::ParseError,
+    nom::{comma_list, parse_complete, sections, IResult},
+    Solution,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{newline, u32 as nom_u32},
+    combinator::{map, opt},
+    error::{context, VerboseError, VerboseErrorKind},
+    sequence::{delimited, separated_pair},
+};
+use rand::{rngs::StdRng, Rng};
+
+/// Compares packets the way the distress signal wants them ordered
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input<'a> = Input;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+/// A packet, or one of the values inside it. Packets are ordered the way the puzzle compares
+/// them, a scalar being compared against a list as if it were a list of just itself.
+#[derive(PartialEq, Eq, Clone)]
+pub enum Packet {
+    /// A plain number
+    Scalar(u32),
+    /// `[...]`, any number of values
+    List(Vec<Self>),
+}
+
+impl Packet {
+    fn as_slice(&self) -> &[Self] {
+        match self {
+            x @ Packet::Scalar(_) => std::slice::from_ref(x),
+            Packet::List(l) => l.as_slice(),
+        }
+    }
+}
+
+impl Debug for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Scalar(arg0) => write!(f, "{arg0}"),
+            Self::List(arg0) => {
+                write!(f, "[")?;
+                for x in arg0 {
+                    write!(f, "{x:?},")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// The packet the way it's written in the puzzle input
+impl Display for Packet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Scalar(x) => write!(f, "{x}"),
+            Self::List(list) => {
+                write!(f, "[")?;
+                for (n, x) in list.iter().enumerate() {
+                    if n > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{x}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl FromStr for Packet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_complete(s, parse_packet)
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (Packet::Scalar(x), Packet::Scalar(y)) => x.cmp(y),
+            _ => self.as_slice().cmp(other.as_slice()),
+        }
+    }
+}
+/// The pairs of packets, in order
+pub type Input = Vec<(Packet, Packet)>;
+
+/// Packets nested deeper than this are turned away before they use up the stack
+const MAX_DEPTH: usize = 256;
+
+fn parse_packet(input: &str) -> IResult<&str, Packet> {
+    nested_packet(input, 0)
+}
+
+fn nested_packet(input: &str, depth: usize) -> IResult<&str, Packet> {
+    if depth > MAX_DEPTH {
+        return Err(nom::Err::Failure(VerboseError {
+            errors: vec![(input, VerboseErrorKind::Context("packet nested too deeply"))],
+        }));
+    }
+
+    context(
+        "packet",
+        map(
+            delimited(
+                tag("["),
+                map(
+                    opt(comma_list(alt((map(nom_u32, Packet::Scalar), |s| {
+                        nested_packet(s, depth + 1)
+                    })))),
+                    Option::unwrap_or_default,
+                ),
+                tag("]"),
+            ),
+            Packet::List,
+        ),
+    )(input)
+}
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_complete(
+        input,
+        sections(separated_pair(parse_packet, newline, parse_packet)),
+    )
+}
+
+/// `size` pairs of packets, each a list of up to five values nested no more than four deep
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    fn list(rng: &mut StdRng, depth: usize) -> String {
+        let values: Vec<String> = (0..rng.gen_range(0..=5))
+            .map(|_| {
+                if depth < 4 && rng.gen_ratio(1, 3) {
+                    list(rng, depth + 1)
+                } else {
+                    rng.gen_range(0..=10).to_string()
+                }
+            })
+            .collect();
+        format!("[{}]", values.join(","))
+    }
+
+    let pairs: Vec<String> = (0..size.max(1))
+        .map(|_| format!("{}\n{}\n", list(rng, 0), list(rng, 0)))
+        .collect();
+    pairs.join("\n")
+}
+
+fn print(pairs: &Input) -> String {
+    let pairs: Vec<String> = pairs
+        .iter()
+        .map(|(left, right)| format!("{left}\n{right}\n"))
+        .collect();
+    pairs.join("\n")
+}
+
+fn problem1(pairs: &Input) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (idx, (left, right))| {
+            acc + if left <= right { idx + 1 } else { 0 }
+        })
+}
+
+fn problem2(pairs: &Input) -> usize {
+    let mut signals: Vec<&Packet> = pairs.iter().flat_map(|(p1, p2)| vec![p1, p2]).collect();
+
+    let divider_2 = &parse_packet("[[2]]").unwrap().1;
+    let divider_6 = &parse_packet("[[6]]").unwrap().1;
+
+    signals.push(divider_2);
+    signals.push(divider_6);
+
+    signals.sort();
+
+    signals.iter().enumerate().fold(1, |acc, (idx, x)| {
+        acc * if *x == divider_2 || *x == divider_6 {
+            idx + 1
+        } else {
+            1
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use crate::{parse, parse_packet, Day13, Packet, MAX_DEPTH};
+
+    common::examples!(Day13);
+    common::properties!(Day13, [1, 10, 150]);
+
+    fn arbitrary_packet() -> impl Strategy<Value = Packet> {
+        let scalar = any::<u32>().prop_map(Packet::Scalar);
+        let packet = scalar.prop_recursive(6, 64, 5, |inner| {
+            prop::collection::vec(inner, 0..5).prop_map(Packet::List)
+        });
+        // the outside of a packet is always a list
+        prop::collection::vec(packet, 0..5).prop_map(Packet::List)
+    }
+
+    #[test]
+    fn ordering() {
+        let packet = |s: &str| s.parse::<Packet>().unwrap();
+        assert!(packet("[1,1,3,1,1]") < packet("[1,1,5,1,1]"));
+        assert!(packet("[[1],[2,3,4]]") < packet("[[1],4]"));
+        assert!(packet("[[[]]]") > packet("[[]]"));
+        assert!("[1,2".parse::<Packet>().is_err());
+    }
+
+    #[test]
+    fn too_deep() {
+        let deep = "[".repeat(MAX_DEPTH * 4) + &"]".repeat(MAX_DEPTH * 4);
+        assert!(parse(&format!("{deep}\n[]\n")).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn packets_round_trip(packet in arbitrary_packet()) {
+            let text = packet.to_string();
+            prop_assert_eq!(parse_packet(&text).unwrap(), ("", packet));
+        }
+    }
+}
@@ -1,46 +1,72 @@
-use common::map::{Coord, Map};
-use std::{collections::BTreeSet, fmt::Debug};
-
+//! The cave as sand falls into it: the rock paths from the scan, drawn out into a grid of tiles
+
+use common::{
+    map::Coord,
+    render::Render,
+    sparse::{Bounds, Point, SparseGrid},
+};
+use std::{
+    collections::BTreeSet,
+    fmt::{Debug, Display},
+};
+
+/// Whatever is taking up a spot in the cave
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Tile {
-    Air,
+    /// Sand that has come to rest
     Sand,
+    /// Part of a rock path
     Rock,
+    /// Where the sand pours in from
     Source,
 }
 
-impl Debug for Tile {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Tile {
+    /// How the tile is drawn in the puzzle text
+    pub fn symbol(&self) -> char {
         match self {
-            Self::Air => write!(f, "."),
-            Self::Sand => write!(f, "o"),
-            Self::Rock => write!(f, "#"),
-            Self::Source => write!(f, "+"),
+            Self::Sand => 'o',
+            Self::Rock => '#',
+            Self::Source => '+',
         }
     }
 }
 
-#[derive(Debug)]
+impl Debug for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// One line of the scan, a path of straight lines of rock
+#[derive(Debug, PartialEq, Eq)]
 pub struct Path {
+    /// The corners, each a straight horizontal or vertical line from the one before
     pub segments: Vec<Coord>,
 }
 
 impl Path {
+    /// A path through the given corners
     pub fn new(segments: Vec<Coord>) -> Self {
         Self { segments }
     }
+
+    /// The leftmost point of the path. Panics if there aren't any corners.
     pub fn get_min_x(&self) -> usize {
         self.segments.iter().map(|&(x, _)| x).min().unwrap()
     }
 
+    /// The rightmost point of the path. Panics if there aren't any corners.
     pub fn get_max_x(&self) -> usize {
         self.segments.iter().map(|&(x, _)| x).max().unwrap()
     }
 
+    /// The lowest point of the path. Panics if there aren't any corners.
     pub fn get_max_y(&self) -> usize {
         self.segments.iter().map(|&(_, y)| y).max().unwrap()
     }
 
+    /// Every spot the path covers with rock
     pub fn all_points(&self) -> BTreeSet<Coord> {
         let mut points = BTreeSet::new();
         for pair in self.segments.windows(2) {
@@ -65,74 +91,103 @@ impl Path {
     }
 }
 
-trait CaveCoord {
-    fn translate(&self, min_x: usize) -> Coord;
-}
-impl CaveCoord for Coord {
-    // normalize a point so that x is a value relative to the minimum x as the zero
-    fn translate(&self, min_x: usize) -> Coord {
-        let (x, y) = self;
-        let new_x = x - min_x;
-        (new_x, *y)
-    }
-}
-
+/// The cave, with its rock, sand and the source of the sand
 pub struct CaveMap {
-    pub map: Map<Tile>,
-    pub source: Coord,
+    /// Everything that isn't air
+    pub tiles: SparseGrid<Tile>,
+    /// Where the sand pours in from
+    pub source: Point,
+    /// The lowest rock there is, anything that falls past it without a floor is gone for good
+    pub lowest_rock: i64,
+    /// The floor stretches out forever in both directions, so it isn't stored as tiles
+    pub floor: Option<i64>,
+    /// Everywhere sand could ever come to rest, which doesn't change as the sand piles up
+    pub view: Bounds,
 }
 
 impl CaveMap {
+    /// A cave with nothing but the rock from the scan, and a floor below it if there is one
     pub fn new(paths: &[Path], has_floor: bool) -> Self {
-        let min_y = 0;
-        let mut max_y: usize = 0;
-        let mut max_x: usize = 0;
-        let mut min_x: usize = usize::MAX;
+        let mut tiles = SparseGrid::new();
 
-        // find the bounds of the map
+        // place all the rocks
         for path in paths {
-            for &(x, y) in &path.segments {
-                max_y = max_y.max(y);
-                max_x = max_x.max(x);
-                min_x = min_x.min(x);
+            for (x, y) in path.all_points() {
+                tiles.insert(Point::new(x as i64, y as i64), Tile::Rock);
             }
         }
 
-        // the floor is 2 levels below our maximum y point, we also need to extend the x direction "infinitely"
-        if has_floor {
-            const EDGE_PADDING: usize = 200;
+        let lowest_rock = tiles.bounds().map(|b| b.max_y).unwrap_or(0);
+
+        // the floor is 2 levels below our maximum y point
+        let floor = has_floor.then_some(lowest_rock + 2);
 
-            min_x -= EDGE_PADDING;
-            max_x += EDGE_PADDING;
-            max_y += 2;
+        // place the source
+        let source = Point::new(500, 0);
+        tiles.insert(source, Tile::Source);
+
+        // sand can only settle right next to rock or other sand, and on a floor it can't spread
+        // any further than a pyramid from the source
+        let mut view = tiles.bounds().unwrap_or(Bounds::point(source));
+        view.include(Point::new(view.min_x - 1, view.max_y));
+        view.include(Point::new(view.max_x + 1, view.max_y));
+        if let Some(floor) = floor {
+            let spread = floor - source.y;
+            view.include(Point::new(source.x - spread, floor));
+            view.include(Point::new(source.x + spread, floor));
         }
 
-        let width = max_x - min_x;
-        let height = max_y - min_y;
+        Self {
+            tiles,
+            source,
+            lowest_rock,
+            floor,
+            view,
+        }
+    }
 
-        // init the map with air
-        let tiles = vec![vec![Tile::Air; width + 1]; height + 1];
-        let mut map = Map::new(tiles);
+    /// Whether sand could fall into this spot
+    pub fn is_free(&self, p: Point) -> bool {
+        self.floor != Some(p.y) && !self.tiles.contains(p)
+    }
 
-        // place all the rocks
-        for path in paths {
-            for point in path.all_points() {
-                let rock = point.translate(min_x);
-                map.set(rock, Tile::Rock);
-            }
+    /// What's at a spot, counting the floor as rock
+    pub fn tile(&self, p: Point) -> Option<Tile> {
+        match self.tiles.get(p) {
+            Some(&tile) => Some(tile),
+            None if self.floor == Some(p.y) => Some(Tile::Rock),
+            None => None,
         }
+    }
+}
 
-        // draw the floor across the entire last row
-        if has_floor {
-            for floor_x in 0..=width {
-                map.set((floor_x, max_y), Tile::Rock);
-            }
-        }
+impl Render for CaveMap {
+    type Cell<'a> = Option<Tile>;
 
-        // place the source
-        let source = (500, 0).translate(min_x);
-        map.set(source, Tile::Source);
+    fn size(&self) -> (usize, usize) {
+        (self.view.width() as usize, self.view.height() as usize)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<Tile> {
+        self.tile(Point::new(
+            self.view.min_x + x as i64,
+            self.view.min_y + y as i64,
+        ))
+    }
+}
+
+impl Display for CaveMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(mut bounds) = self.tiles.bounds() else {
+            return Ok(());
+        };
+        if let Some(floor) = self.floor {
+            bounds.include(Point::new(bounds.min_x, floor));
+        }
 
-        Self { map, source }
+        let rendered = self.tiles.render_within(bounds, |p, _| {
+            self.tile(p).map_or('.', |tile| tile.symbol())
+        });
+        write!(f, "{rendered}")
     }
 }
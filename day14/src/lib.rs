@@ -0,0 +1,218 @@
+//! Day 14: Regolith Reservoir
+//!
+//! Rock [Path]s in a cave that sand falls into; part 1 counts the sand that
+//! settles before it falls into the abyss, part 2 until the source is blocked by the floor.
+
+#![warn(missing_docs)]
+
+use cavemap::{CaveMap, Path, Tile};
+use common::{
+    animate::{Playback, Steps},
+    error::ParseError,
+    generate,
+    nom::{coord, parse_lines},
+    point::Dir8,
+    render::{Frame, Recorder, Render, Rgb},
+    Part, Solution,
+};
+use nom::{bytes::complete::tag, combinator::map, error::context, multi::separated_list1};
+
+pub mod cavemap;
+use rand::{rngs::StdRng, Rng};
+
+/// Pours sand into the cave one unit at a time
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input<'a> = Input;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+
+    fn render(input: &Self::Input<'_>, part: Part) -> Option<Vec<Frame>> {
+        Some(render(input, part == Part::Two))
+    }
+
+    fn animate<'a>(input: &'a Self::Input<'_>, part: Part) -> Option<Box<dyn Playback + 'a>> {
+        // a grain of sand at a time
+        Some(Box::new(Steps::new(
+            move || CaveMap::new(input, part == Part::Two),
+            |map| simulate_sand(map) == SandResult::Settled,
+        )))
+    }
+}
+
+/// The scan of the cave's rock, one path per line
+pub type Input = Vec<Path>;
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_lines(
+        input,
+        context("path", map(separated_list1(tag(" -> "), coord), Path::new)),
+    )
+}
+
+#[derive(PartialEq, Eq)]
+enum SandResult {
+    Clogged,
+    Abyss,
+    Settled,
+}
+
+/// `size` paths of rock scattered below the sand's source, each turning a corner up to three times
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let paths: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let (mut x, mut y) = (rng.gen_range(440..=560_i32), rng.gen_range(10..=160_i32));
+            let mut points = vec![format!("{x},{y}")];
+            let mut across = rng.gen();
+            for _ in 0..rng.gen_range(1..=4) {
+                let length = rng.gen_range(1..=8) * if rng.gen() { 1 } else { -1 };
+                if across {
+                    x = (x + length).clamp(420, 580);
+                } else {
+                    y = (y + length).clamp(5, 170);
+                }
+                across = !across;
+                points.push(format!("{x},{y}"));
+            }
+            points.join(" -> ")
+        })
+        .collect();
+    generate::lines(paths)
+}
+
+fn print(paths: &Input) -> String {
+    generate::lines(paths.iter().map(|path| {
+        let points: Vec<String> = path
+            .segments
+            .iter()
+            .map(|(x, y)| format!("{x},{y}"))
+            .collect();
+        points.join(" -> ")
+    }))
+}
+
+fn simulate_sand(input: &mut CaveMap) -> SandResult {
+    // every sand particle starts at the source
+    let mut sand = input.source;
+
+    // without a floor, anything below the lowest rock keeps falling forever
+    while input.floor.is_some() || sand.y < input.lowest_rock {
+        // try straight down first, then diagonally left, then diagonally right
+        let next = [Dir8::Down, Dir8::DownLeft, Dir8::DownRight]
+            .into_iter()
+            .map(|dir| sand + dir)
+            .find(|&p| input.is_free(p));
+
+        if let Some(next) = next {
+            sand = next;
+        } else {
+            // we can't move down, diagonal left, or diagonal right so we settle here
+            input.tiles.insert(sand, Tile::Sand);
+            return if sand == input.source {
+                SandResult::Clogged
+            } else {
+                SandResult::Settled
+            };
+        }
+    }
+    // we're off the map, so return Abyss
+    SandResult::Abyss
+}
+
+fn palette(tile: Option<Tile>) -> Rgb {
+    match tile {
+        Some(Tile::Rock) => [110, 100, 90],
+        Some(Tile::Sand) => [230, 190, 100],
+        Some(Tile::Source) => [60, 160, 90],
+        None => [20, 20, 30],
+    }
+}
+
+/// Pour sand until it stops, with a frame every so many grains. The view never changes, so the
+/// frames can be drawn as they're recorded instead of keeping whole maps around.
+fn render(input: &Input, has_floor: bool) -> Vec<Frame> {
+    let mut map = CaveMap::new(input, has_floor);
+    let mut recorder = Recorder::default();
+    loop {
+        let result = simulate_sand(&mut map);
+        if result != SandResult::Settled {
+            break;
+        }
+        recorder.step(|| map.frame(palette));
+    }
+    recorder.finish(map.frame(palette))
+}
+
+fn problem1(input: &Input) -> u32 {
+    let mut grains = 0;
+    let mut map = CaveMap::new(input, false);
+    while simulate_sand(&mut map) != SandResult::Abyss {
+        grains += 1;
+    }
+
+    grains
+}
+
+fn problem2(input: &Input) -> u32 {
+    let mut grains = 0;
+    let mut map = CaveMap::new(input, true);
+    while simulate_sand(&mut map) != SandResult::Clogged {
+        grains += 1;
+    }
+
+    grains + 1
+}
+
+#[cfg(test)]
+mod test {
+    use common::test::get_raw_input;
+
+    use crate::{cavemap::CaveMap, parse, Day14};
+
+    common::examples!(Day14);
+    common::properties!(Day14, [1, 10, 150]);
+
+    #[test]
+    fn render() {
+        let input = get_raw_input();
+        let input = parse(&input).unwrap();
+        let map = CaveMap::new(&input, false);
+        assert_eq!(
+            map.to_string(),
+            "......+...
+..........
+..........
+..........
+....#...##
+....#...#.
+..###...#.
+........#.
+........#.
+#########.
+"
+        )
+    }
+}
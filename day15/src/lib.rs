@@ -0,0 +1,241 @@
+//! Day 15: Beacon Exclusion Zone
+//!
+//! Sensors and their closest beacons, kept in an [Input]; part 1 counts where a beacon can't be
+//! on one row, part 2 finds the only place it can be.
+
+#![warn(missing_docs)]
+
+use common::{
+    error::ParseError,
+    generate,
+    interval::{Interval, IntervalSet},
+    nom::{labelled_point2, parse_lines},
+    point::Point2,
+    Solution,
+};
+use nom::{
+    bytes::complete::tag,
+    combinator::map_opt,
+    error::context,
+    sequence::{preceded, separated_pair},
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+use rayon::prelude::*;
+
+/// Works out where the sensors rule out a beacon
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input<'a> = Input;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input, 2_000_000)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input, 4_000_000)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+/// Every sensor, and the beacons they found
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input {
+    sensors: Vec<Sensor>,
+    beacons: Vec<Point2<i64>>,
+}
+
+impl Input {
+    /// Every spot on the row that's close enough to a sensor to have been seen by it
+    fn get_coverage(&self, row: i64) -> IntervalSet<i64> {
+        self.sensors
+            .iter()
+            .filter_map(|s| s.get_coverage(row))
+            .collect()
+    }
+}
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let sensors: Vec<Sensor> = parse_lines(
+        input,
+        context(
+            "sensor",
+            map_opt(
+                preceded(
+                    tag("Sensor at "),
+                    separated_pair(
+                        labelled_point2,
+                        tag(": closest beacon is at "),
+                        labelled_point2,
+                    ),
+                ),
+                |(position, beacon)| Sensor::new(position, beacon),
+            ),
+        ),
+    )?;
+
+    let mut beacons: Vec<_> = sensors.iter().map(|s| s.beacon).collect();
+    beacons.sort();
+    beacons.dedup();
+
+    Ok(Input { sensors, beacons })
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Sensor {
+    position: Point2<i64>,
+    beacon: Point2<i64>,
+    dist: i64,
+}
+
+impl Sensor {
+    /// None if the beacon is too far away to measure how far away it is
+    fn new(position: Point2<i64>, beacon: Point2<i64>) -> Option<Sensor> {
+        let dx = position.x.checked_sub(beacon.x)?.checked_abs()?;
+        let dy = position.y.checked_sub(beacon.y)?.checked_abs()?;
+        Some(Sensor {
+            position,
+            beacon,
+            dist: dx.checked_add(dy)?,
+        })
+    }
+
+    fn get_coverage(&self, row: i64) -> Option<Interval<i64>> {
+        let &Sensor {
+            position: Point2 { x, y },
+            dist,
+            ..
+        } = self;
+        // get the vertical distance between these two points
+        let y_distance = row.abs_diff(y) as i64;
+        // the horizontal difference is the rest of the manhattan distance, and if there's none
+        // left then the row is out of range of this sensor
+        let remaining_x_distance = dist - y_distance;
+
+        Interval::new(x - remaining_x_distance, x + remaining_x_distance)
+    }
+}
+
+/// `size` sensors (at least four) around one hidden beacon somewhere in part 2's search area. Four
+/// sensors further away than the search area is wide sit diagonally around it, each reaching just
+/// short of it, which between them covers everything else. The rest are scattered about and stop
+/// short of it too.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    const AREA: i64 = 4_000_000;
+    let hidden = Point2::new(rng.gen_range(0..=AREA), rng.gen_range(0..=AREA));
+
+    let far = AREA + rng.gen_range(1..=AREA / 4);
+    let mut pairs: Vec<(Point2<i64>, Point2<i64>)> = [(1, 1), (-1, 1), (1, -1), (-1, -1)]
+        .into_iter()
+        .map(|(dx, dy)| {
+            let sensor = hidden + Point2::new(dx * far, dy * far);
+            // one closer to the sensor than the hidden beacon is
+            (sensor, hidden + Point2::new(0, dy))
+        })
+        .collect();
+
+    while pairs.len() < size {
+        let sensor = Point2::new(rng.gen_range(0..=AREA), rng.gen_range(0..=AREA));
+        let reach = sensor.manhattan(hidden) - 1;
+        if reach < 1 {
+            continue;
+        }
+        let reach = rng.gen_range(1..=reach.min(AREA / 4));
+        let dx = rng.gen_range(-reach..=reach);
+        let dy = (reach - dx.abs()) * if rng.gen() { 1 } else { -1 };
+        pairs.push((sensor, sensor + Point2::new(dx, dy)));
+    }
+    pairs.shuffle(rng);
+
+    generate::lines(pairs.into_iter().map(|(sensor, beacon)| {
+        format!(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            sensor.x, sensor.y, beacon.x, beacon.y
+        )
+    }))
+}
+
+fn print(input: &Input) -> String {
+    generate::lines(input.sensors.iter().map(|s| {
+        format!(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            s.position.x, s.position.y, s.beacon.x, s.beacon.y
+        )
+    }))
+}
+
+fn problem1(input: &Input, row: i64) -> i64 {
+    let coverage = input.get_coverage(row);
+
+    // the beacons that were found there are the only spots that could be a beacon
+    let beacons = input
+        .beacons
+        .iter()
+        .filter(|b| b.y == row && coverage.contains(b.x))
+        .count();
+
+    (coverage.len() - beacons as u64) as i64
+}
+
+/* I completely rewrote the implementation between problem 1 and 2. My initial
+implementation was to generate a set of points which worked on small inputs, and even
+on the big input, it "worked" for a single row.
+
+The better implementation only concerns itself with merging ranges and then I sped that
+up by adding rayon to make things parallel. Rayon might be cheating a bit, but the
+sequential program was relatively fast even without it...and rayon is super easy to add.
+*/
+fn problem2(input: &Input, max_search_area: i64) -> i64 {
+    let search = IntervalSet::from(Interval::new(0, max_search_area).unwrap());
+    let (x, y) = (0..=max_search_area)
+        .into_par_iter()
+        .find_map_any(|row| {
+            // whatever's left on this row after taking out what the sensors can see is the beacon
+            let hidden = search.difference(&input.get_coverage(row));
+            hidden.iter().next().map(|spot| (spot.start(), row))
+        })
+        .unwrap();
+
+    (x * 4_000_000) + y
+}
+
+#[cfg(test)]
+mod test {
+    use common::test::get_raw_input;
+
+    use crate::{parse, problem1, problem2, Day15};
+
+    common::examples!(Day15);
+    common::properties!(Day15, [1, 4, 30]);
+
+    #[test]
+    fn first() {
+        let input = get_raw_input();
+        let input = parse(&input).unwrap();
+        let result = problem1(&input, 10);
+        assert_eq!(result, 26)
+    }
+
+    #[test]
+    fn second() {
+        let input = get_raw_input();
+        let input = parse(&input).unwrap();
+        let result = problem2(&input, 20);
+        assert_eq!(result, 56000011)
+    }
+}
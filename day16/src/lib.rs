@@ -0,0 +1,359 @@
+//! Day 16: Proboscidea Volcanium
+//!
+//! A network of valves and tunnels in [Caves]; the most pressure that can be released in 30
+//! minutes alone, or in 26 with an elephant's help.
+
+#![warn(missing_docs)]
+
+use common::{error::ParseError, generate, search::all_pairs, Solution};
+use ndarray::prelude::*;
+use std::collections::BTreeSet;
+
+mod parser;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+/// Plans which valves to open, alone and with an elephant
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input<'a> = Input;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parser::parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+/// `size` valves (between 2 and 60) joined up into one cave system, with AA among them. About a
+/// third have a working valve, never more than 15 though, since finding the best order to open
+/// them in takes time that grows very quickly with each one.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let count = size.clamp(2, 60);
+    let mut names = generate::names(rng, count, 2, "ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+    match names.iter().position(|n| n == "AA") {
+        Some(aa) => names.swap(0, aa),
+        None => names[0] = "AA".to_owned(),
+    }
+
+    // each valve joins on to one of the few before it so they're all connected in long winding
+    // tunnels like the real caves, and then a few shortcuts between nearby valves
+    let mut tunnels = vec![BTreeSet::new(); count];
+    let mut join = |a: usize, b: usize| {
+        if a != b {
+            tunnels[a].insert(b);
+            tunnels[b].insert(a);
+        }
+    };
+    for v in 1..count {
+        join(v, rng.gen_range(v.saturating_sub(3)..v));
+    }
+    for _ in 0..count / 6 {
+        let v = rng.gen_range(0..count);
+        join(v, (v + rng.gen_range(2..=5)).min(count - 1));
+    }
+
+    let working = ((count - 1) / 3).clamp(1, 15);
+    let mut rates = vec![0; count];
+    for rate in rates.iter_mut().skip(1).take(working) {
+        *rate = rng.gen_range(1..=25);
+    }
+    // AA stays at 0 wherever it ends up
+    rates[1..].shuffle(rng);
+
+    let mut order: Vec<usize> = (0..count).collect();
+    order.shuffle(rng);
+    generate::lines(order.into_iter().map(|v| {
+        let to: Vec<&str> = tunnels[v].iter().map(|&n| names[n].as_str()).collect();
+        let leads = if to.len() == 1 {
+            "tunnel leads to valve"
+        } else {
+            "tunnels lead to valves"
+        };
+        format!(
+            "Valve {} has flow rate={}; {leads} {}",
+            names[v],
+            rates[v],
+            to.join(", ")
+        )
+    }))
+}
+
+/// The valves and the tunnels between them
+pub type Input = Caves;
+/// A valve's place in the input, counting from 0
+pub type ValveId = usize;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Valve {
+    id: ValveId,
+    flow_rate: u32,
+    neighbors: Vec<ValveId>,
+}
+
+/// The valves, the tunnels between them, and how far every valve is from every other
+#[derive(Debug, PartialEq, Eq)]
+pub struct Caves {
+    valves: Vec<Valve>,
+    aa_index: ValveId,
+    distances: Array2<u32>,
+}
+impl Caves {
+    fn new(valves: Vec<Valve>, aa_index: ValveId) -> Caves {
+        let distances = valve_distances(&valves);
+
+        Caves {
+            valves,
+            aa_index,
+            distances,
+        }
+    }
+
+    /// How many valves there are, so their ids run from 0 up to this
+    pub fn len(&self) -> usize {
+        self.valves.len()
+    }
+
+    /// Whether there are no valves at all
+    pub fn is_empty(&self) -> bool {
+        self.valves.is_empty()
+    }
+
+    /// AA, where every search starts
+    pub fn start(&self) -> ValveId {
+        self.aa_index
+    }
+
+    /// How much pressure a valve releases each minute once it's open
+    pub fn flow_rate(&self, valve: ValveId) -> u32 {
+        self.valves[valve].flow_rate
+    }
+
+    /// The valves a tunnel leads to from this one
+    pub fn neighbors(&self, valve: ValveId) -> &[ValveId] {
+        &self.valves[valve].neighbors
+    }
+
+    /// How many minutes it takes to walk from one valve to another, or None if there's no way
+    pub fn distance(&self, from: ValveId, to: ValveId) -> Option<u32> {
+        Some(self.distances[[from, to]]).filter(|&d| d != u32::MAX)
+    }
+
+    fn non_zero_valves(&self) -> Vec<&Valve> {
+        self.valves.iter().filter(|x| x.flow_rate != 0).collect()
+    }
+}
+
+/// The valves in the order they were parsed, so they keep their ids. The names didn't survive
+/// parsing, so each one is named after its id instead, apart from AA.
+fn print(caves: &Caves) -> String {
+    let name = |id: ValveId| {
+        if id == caves.aa_index {
+            return "AA".to_owned();
+        }
+        // counting up from AB in base 26, so nothing else is ever called AA
+        let mut n = id + 1;
+        let mut letters = vec![];
+        while n > 0 || letters.len() < 2 {
+            letters.push((b'A' + (n % 26) as u8) as char);
+            n /= 26;
+        }
+        letters.iter().rev().collect()
+    };
+
+    generate::lines(caves.valves.iter().map(|valve| {
+        let to: Vec<String> = valve.neighbors.iter().map(|&n| name(n)).collect();
+        let leads = if to.len() == 1 {
+            "tunnel leads to valve"
+        } else {
+            "tunnels lead to valves"
+        };
+        format!(
+            "Valve {} has flow rate={}; {leads} {}",
+            name(valve.id),
+            valve.flow_rate,
+            to.join(", ")
+        )
+    }))
+}
+
+fn valve_distances(valves: &[Valve]) -> Array2<u32> {
+    let pairs = all_pairs(0..valves.len(), |&v| {
+        valves[v].neighbors.iter().map(|&n| (n, 1))
+    });
+
+    // valves we can't get to are further away than we'll ever have time for
+    Array2::from_shape_fn((valves.len(), valves.len()), |(i, j)| {
+        pairs.get(&(i, j)).copied().unwrap_or(u32::MAX)
+    })
+}
+
+type Path<'a> = Vec<&'a Valve>;
+
+/* A recursive DFS to go through all the remaining valves that we have time to open. We skip a ton of the problem
+space by using the distances between every pair of valves that we precomputed because we're able to omit visiting
+every node that has a valve that's zero. What we'll end up with is a Vec of Paths that we can score after.
+
+It would likely be faster to keep track of the scores on the way back up and omit whole sections that are worse...but I
+think this runs Fast Enough (tm). */
+fn find_all_paths<'a>(
+    caves: &'a Caves,
+    position: usize,
+    opened_valves: Vec<&'a Valve>,
+    time_left: u32,
+) -> Vec<Path<'a>> {
+    // get a list of the non-opened valves that we have time to get to
+    let remaining: Vec<(&Valve, u32)> = caves
+        .non_zero_valves()
+        .iter()
+        .filter_map(|next| {
+            let dist = caves.distances[[position, next.id]];
+            if !opened_valves.contains(next) && dist < time_left {
+                Some((*next, dist))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let current_path = opened_valves.clone();
+    // we want to go over all the remaining paths
+    let mut remaining_paths: Vec<Path> = remaining
+        .iter()
+        .flat_map(|(x, dist)| {
+            // get the remaining time to move there and open a valve
+            let new_time_left = time_left - dist - 1;
+
+            // push the new valve onto the stack
+            let mut new_opened_valves = opened_valves.clone();
+            new_opened_valves.push(*x);
+
+            // and recursively find all the remaining paths
+            find_all_paths(caves, x.id, new_opened_valves, new_time_left)
+        })
+        .collect();
+
+    remaining_paths.push(current_path);
+    remaining_paths
+}
+
+fn path_score(caves: &Caves, path: &Path, time_left: u32) -> u32 {
+    let (_, _, score) = path.iter().fold(
+        (caves.aa_index, time_left, 0),
+        |(pos, time_left, score), v| {
+            // get the remaining time to get to this node and then open the valve
+            let time_left = time_left - caves.distances[[pos, v.id]] - 1;
+            // calculate the new score based off the steam we'll release from this valve
+            let score = score + v.flow_rate * time_left;
+
+            (v.id, time_left, score)
+        },
+    );
+
+    score
+}
+
+fn problem1(caves: &Input) -> u32 {
+    let time_left = 30;
+
+    // find all the possible paths through the maze
+    let all_paths = find_all_paths(caves, caves.aa_index, Vec::new(), time_left);
+    // score all the paths
+    let path_scores = all_paths
+        .iter()
+        .map(|path| path_score(caves, path, time_left));
+
+    // get the max
+    path_scores.max().unwrap()
+}
+
+fn problem2(caves: &Input) -> u32 {
+    let time_left = 26;
+
+    // find all the possible paths through the maze
+    let all_paths = find_all_paths(caves, caves.aa_index, Vec::new(), time_left);
+    // score all the paths
+    let mut path_scores: Vec<(BTreeSet<ValveId>, u32)> = all_paths
+        .iter()
+        .map(|path| {
+            (
+                // we're going to need to do set comparison later, so just make all the paths sets
+                // note: we probably could have done this and the pathing with a bitvec? would that have been faster?
+                path.iter().map(|v| v.id).collect(),
+                path_score(caves, path, time_left),
+            )
+        })
+        .collect();
+
+    // order them by their scores so we can start by comparing best scores
+    path_scores.sort_by(|(_, sa), (_, sb)| sb.cmp(sa));
+
+    let mut answer = 0;
+
+    // there are now two actors exploring possible paths, so we need to model that
+    for human_idx in 0..path_scores.len() {
+        let (human_path, human_score) = &path_scores[human_idx];
+
+        // quit as soon as we found there are no more possible best answers
+        // (the last path has nothing after it to pair with)
+        let best_elephant = path_scores
+            .get(human_idx + 1)
+            .map_or(0, |(_, score)| *score);
+        if human_score + best_elephant < answer {
+            break;
+        }
+
+        // only compare scores that are smaller than our current score
+        (human_idx + 1..path_scores.len()).for_each(|elephant_idx| {
+            let (elephant_path, elephant_score) = &path_scores[elephant_idx];
+
+            // we want the best score where both the human and elephant open disjoint sets of valves
+            if human_path.is_disjoint(elephant_path) {
+                answer = answer.max(human_score + elephant_score);
+            }
+        });
+    }
+
+    answer
+}
+
+#[cfg(test)]
+mod test {
+    use common::{test::get_raw_input, Solution};
+
+    use crate::Day16;
+
+    common::examples!(Day16);
+    common::properties!(Day16, [1, 2, 3, 10, 60]);
+
+    #[test]
+    fn caves() {
+        let caves = Day16::parse(&get_raw_input()).unwrap();
+        assert_eq!(caves.len(), 10);
+        // AA, BB, CC and DD come first, in that order
+        assert_eq!(caves.start(), 0);
+        assert_eq!(caves.flow_rate(0), 0);
+        assert_eq!(caves.flow_rate(3), 20);
+        assert_eq!(caves.neighbors(1), [2, 0]);
+        assert_eq!(caves.distance(0, 0), Some(0));
+        assert_eq!(caves.distance(1, 3), Some(2));
+    }
+}
@@ -2,12 +2,14 @@ use std::collections::BTreeMap;
 
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{alpha1, newline, u32 as nom_u32};
-use nom::combinator::map;
+use nom::character::complete::{alpha1, u32 as nom_u32};
+use nom::error::context;
 use nom::multi::separated_list1;
-use nom::{
-    sequence::{preceded, separated_pair},
-    IResult,
+use nom::sequence::{preceded, separated_pair};
+
+use common::{
+    error::ParseError,
+    nom::{parse_lines, IResult},
 };
 
 use crate::{Input, Valve};
@@ -29,38 +31,53 @@ fn parse_adjacent(input: &str) -> IResult<&str, Vec<&str>> {
     )(input)
 }
 
-pub(crate) fn parse(input: &str) -> Input {
-    let result: IResult<&str, Input> = map(
-        separated_list1(
-            newline,
-            map(
-                separated_pair(parse_valve, tag("; "), parse_adjacent),
-                |((name, flow_rate), neighbors)| (name, flow_rate, neighbors),
-            ),
+pub(crate) fn parse(input: &str) -> Result<Input, ParseError> {
+    let valves = parse_lines(
+        input,
+        context(
+            "valve",
+            separated_pair(parse_valve, tag("; "), parse_adjacent),
         ),
-        |valves| {
-            let indexes: BTreeMap<&str, usize> = valves
-                .iter()
-                .enumerate()
-                .map(|(idx, (name, _, _))| (*name, idx))
-                .collect();
+    )?;
+
+    let indexes: BTreeMap<&str, usize> = valves
+        .iter()
+        .enumerate()
+        .map(|(idx, ((name, _), _))| (*name, idx))
+        .collect();
 
-            let valves: Vec<Valve> = valves
+    // the names are slices of the input, so an unknown one can be pointed at directly
+    let index = |name: &str| {
+        indexes
+            .get(name)
+            .copied()
+            .ok_or_else(|| ParseError::at(input, name, format!("unknown valve {name}")))
+    };
+
+    let valves = valves
+        .iter()
+        .enumerate()
+        .map(|(id, ((_name, flow_rate), neighbor_strings))| {
+            let neighbors = neighbor_strings
                 .iter()
-                .enumerate()
-                .map(|(id, (_name, flow_rate, neighbor_strings))| {
-                    let neighbors = neighbor_strings.iter().map(|n| indexes[n]).collect();
-                    Valve {
-                        id,
-                        flow_rate: *flow_rate,
-                        neighbors,
-                    }
-                })
-                .collect();
+                .map(|n| index(n))
+                .collect::<Result<_, _>>()?;
+
+            Ok(Valve {
+                id,
+                flow_rate: *flow_rate,
+                neighbors,
+            })
+        })
+        .collect::<Result<Vec<Valve>, ParseError>>()?;
 
-            crate::Caves::new(valves, indexes["AA"])
-        },
-    )(input);
+    let aa_index = indexes.get("AA").copied().ok_or_else(|| {
+        ParseError::at(
+            input,
+            &input[input.len()..],
+            "there is no valve AA to start from",
+        )
+    })?;
 
-    result.unwrap().1
+    Ok(crate::Caves::new(valves, aa_index))
 }
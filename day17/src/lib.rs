@@ -0,0 +1,488 @@
+//! Day 17: Pyroclastic Flow
+//!
+//! Rocks falling into a chamber and pushed around by [Jet]s of gas; how tall the tower is after
+//! 2022 rocks, then after a trillion.
+
+#![warn(missing_docs)]
+
+use std::{
+    fmt::Display,
+    ops::{BitAnd, BitOrAssign, Range},
+};
+
+use bitvec::{macros::internal::funty::Fundamental, prelude::*};
+use common::{
+    animate::{Playback, Steps},
+    cycle::detect,
+    error::ParseError,
+    nom::parse_complete,
+    render::{Frame, Recorder, Render},
+    Part, Solution,
+};
+use nom::{
+    branch::alt, character::complete::char, combinator::value, error::context, multi::many1,
+};
+use rand::{rngs::StdRng, Rng};
+
+/// Drops rocks into the chamber and measures the tower
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input<'a> = Input;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+
+    fn render(input: &Self::Input<'_>, part: Part) -> Option<Vec<Frame>> {
+        Some(render(input, part))
+    }
+
+    fn animate<'a>(input: &'a Self::Input<'_>, part: Part) -> Option<Box<dyn Playback + 'a>> {
+        let count = drop_count(input, part);
+        Some(Box::new(Steps::new(
+            move || Drops::new(input),
+            move |drops| drops.dropped < count && drops.next().is_some(),
+        )))
+    }
+}
+
+/// The jets, in the order they blow
+pub type Input = Vec<Jet>;
+
+/// Which way a jet of gas pushes the falling rock
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Jet {
+    /// `<`
+    Left,
+    /// `>`
+    Right,
+}
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_complete(
+        input,
+        context(
+            "jets",
+            many1(alt((
+                value(Jet::Left, char('<')),
+                value(Jet::Right, char('>')),
+            ))),
+        ),
+    )
+}
+
+/// `size` random jets of hot gas
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let jets: String = (0..size.max(1))
+        .map(|_| if rng.gen() { '<' } else { '>' })
+        .collect();
+    jets + "\n"
+}
+
+fn print(jets: &Input) -> String {
+    let jets: String = jets
+        .iter()
+        .map(|jet| match jet {
+            Jet::Left => '<',
+            Jet::Right => '>',
+        })
+        .collect();
+    jets + "\n"
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RockKind {
+    Horizontal,
+    Plus,
+    L,
+    Vertical,
+    Square,
+}
+
+impl From<RockKind> for Rock {
+    fn from(val: RockKind) -> Self {
+        let rock = match val {
+            // I could calculate these as hex values, but I like the visual representation
+            RockKind::Horizontal => bitvec![u8, Lsb0;
+                0,0,1,1,1,1,0,
+                0,0,0,0,0,0,0,
+                0,0,0,0,0,0,0,
+                0,0,0,0,0,0,0,
+            ],
+            RockKind::Plus => bitvec![u8, Lsb0;
+                0,0,0,1,0,0,0,
+                0,0,1,1,1,0,0,
+                0,0,0,1,0,0,0,
+                0,0,0,0,0,0,0,
+            ],
+            RockKind::L => bitvec![u8, Lsb0;
+                0,0,1,1,1,0,0,
+                0,0,0,0,1,0,0,
+                0,0,0,0,1,0,0,
+                0,0,0,0,0,0,0,
+            ],
+            RockKind::Vertical => bitvec![u8, Lsb0;
+                0,0,1,0,0,0,0,
+                0,0,1,0,0,0,0,
+                0,0,1,0,0,0,0,
+                0,0,1,0,0,0,0,
+            ],
+            RockKind::Square => bitvec![u8, Lsb0;
+                0,0,1,1,0,0,0,
+                0,0,1,1,0,0,0,
+                0,0,0,0,0,0,0,
+                0,0,0,0,0,0,0,
+            ],
+        };
+
+        Rock { bits: rock }
+    }
+}
+
+impl RockKind {
+    fn all_kinds() -> Vec<RockKind> {
+        vec![
+            Self::Horizontal,
+            Self::Plus,
+            Self::L,
+            Self::Vertical,
+            Self::Square,
+        ]
+    }
+}
+struct Rock {
+    bits: BitVec<u8>,
+}
+
+impl Rock {
+    fn blow(&mut self, jet: &Jet, tower: &Tower, height: usize) {
+        let left_wall = bitvec![u8, Lsb0;
+                1,0,0,0,0,0,0,
+                1,0,0,0,0,0,0,
+                1,0,0,0,0,0,0,
+                1,0,0,0,0,0,0,
+        ];
+        let right_wall = bitvec![u8, Lsb0;
+                0,0,0,0,0,0,1,
+                0,0,0,0,0,0,1,
+                0,0,0,0,0,0,1,
+                0,0,0,0,0,0,1,
+        ];
+
+        let on_left_wall = self.bits.clone().bitand(left_wall).any();
+        let on_right_wall = self.bits.clone().bitand(right_wall).any();
+
+        let mut new_bits = self.bits.clone();
+        match jet {
+            Jet::Left if !on_left_wall => &new_bits.shift_left(1),
+            Jet::Right if !on_right_wall => &new_bits.shift_right(1),
+            _ => {
+                return;
+            }
+        };
+
+        let rock_collision = tower.collision(&new_bits, height);
+        if rock_collision {
+            return;
+        }
+
+        self.bits = new_bits;
+    }
+}
+impl Display for Rock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for n in (0..4).rev() {
+            let range = (n * Tower::WIDTH)..(n * Tower::WIDTH + 7);
+            let x = &self.bits[range];
+            write!(f, "|")?;
+            for y in x {
+                let y = if y.as_bool() { "." } else { "#" };
+                write!(f, "{y}")?;
+            }
+            writeln!(f, "|")?;
+        }
+        writeln!(f)
+    }
+}
+
+struct Tower {
+    bits: BitVec<u8>,
+}
+
+impl Tower {
+    const WIDTH: usize = 7;
+
+    fn new() -> Tower {
+        // put a solid floor at the bottom of the tower
+        let b = bitvec![u8, Lsb0;];
+        Tower::from_bits(b)
+    }
+
+    fn from_bits(bits: BitVec<u8>) -> Tower {
+        Tower { bits }
+    }
+
+    fn grow(&mut self) -> usize {
+        let b = bitvec![u8, Lsb0; 0; 4*Tower::WIDTH];
+        self.bits.extend(b);
+        self.get_height() + 3
+    }
+
+    fn get_height(&self) -> usize {
+        (self.bits.len() - self.bits.trailing_zeros()).div_ceil(Tower::WIDTH)
+    }
+
+    fn get_row_range(&self, row: usize) -> Range<usize> {
+        let start_index = row * Tower::WIDTH;
+        let end_index = start_index + Tower::WIDTH;
+
+        start_index..end_index
+    }
+
+    fn get_tower_slice(&self, row: usize) -> Range<usize> {
+        let start_index = row * Tower::WIDTH;
+        let end_index = self.bits.len().min(start_index + (4 * Tower::WIDTH));
+
+        start_index..end_index
+    }
+
+    /// The top `depth` rows, top first, each as a byte with a bit per column. Anything below the
+    /// bottom of the tower is the floor, so it's solid.
+    fn top_rows(&self, depth: usize) -> Vec<u8> {
+        let height = self.get_height();
+        (0..depth)
+            .map(|below| match height.checked_sub(below + 1) {
+                Some(row) => self.bits[self.get_row_range(row)].load::<u8>(),
+                None => u8::MAX,
+            })
+            .collect()
+    }
+
+    fn collision(&self, rock: &BitSlice<u8>, height: usize) -> bool {
+        let r = self.get_tower_slice(height);
+        let result = self.bits[r].to_bitvec().bitand(rock);
+
+        result.any()
+    }
+
+    fn merge(&mut self, rock: &mut Rock, height: usize) {
+        let r = self.get_tower_slice(height);
+
+        self.bits[r].bitor_assign(&rock.bits);
+    }
+}
+
+impl Display for Tower {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in (0..=self.get_height()).rev() {
+            let r = self.get_row_range(row);
+            let x = &self.bits[r];
+            write!(f, "{row:3} |")?;
+            for y in x {
+                let y = if y.as_bool() { "#" } else { "." };
+                write!(f, "{y}")?;
+            }
+            writeln!(f, "|")?;
+        }
+        writeln!(f, "Tower height = {}", self.get_height())
+    }
+}
+
+/// The top few rows of the tower, which is all there is to see once it gets tall
+struct Top<'t> {
+    tower: &'t Tower,
+    rows: usize,
+}
+
+impl Render for Top<'_> {
+    type Cell<'a>
+        = bool
+    where
+        Self: 'a;
+
+    fn size(&self) -> (usize, usize) {
+        (Tower::WIDTH, self.rows)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> bool {
+        // until the tower is taller than the view, the view sits on the ground
+        let row = self.tower.get_height().max(self.rows) - 1 - y;
+        self.tower
+            .bits
+            .get(row * Tower::WIDTH + x)
+            .is_some_and(|b| *b)
+    }
+}
+
+/// Everything that decides how the rest of the rocks will fall: which rock and jet come next, and
+/// the top of the tower down to the deepest any rock has reached so far.
+///
+/// If two states match, no rock in between went any deeper than the rows they share, so every rock
+/// after the second falls exactly like the one after the first did and the tower repeats from
+/// there. Whatever is further down (like an empty column that only ever gets deeper when every jet
+/// blows the same way) can't change that, so it's left out.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct State {
+    rock_index: usize,
+    jet_index: usize,
+    top: Vec<u8>,
+}
+
+/// Drops rocks one at a time, yielding the state and the height of the tower after each one
+struct Drops<'a> {
+    jets: &'a Input,
+    tower: Tower,
+    rock_index: usize,
+    jet_index: usize,
+    /// How many rocks have come to rest so far
+    dropped: usize,
+    /// The most rows below the top of the tower any rock has looked at on its way down, counting
+    /// the floor as the row under the bottom one
+    reach: usize,
+}
+
+impl<'a> Drops<'a> {
+    fn new(jets: &'a Input) -> Drops<'a> {
+        Drops {
+            jets,
+            tower: Tower::new(),
+            rock_index: 0,
+            jet_index: 0,
+            dropped: 0,
+            reach: 0,
+        }
+    }
+}
+
+impl Iterator for Drops<'_> {
+    type Item = (State, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let kinds = RockKind::all_kinds();
+        let mut rock: Rock = kinds[self.rock_index].into();
+        self.rock_index = (self.rock_index + 1) % kinds.len();
+
+        let top = self.tower.get_height();
+        let mut height = self.tower.grow();
+        loop {
+            // blow the rock one way or another first
+            let jet = &self.jets[self.jet_index];
+            self.jet_index = (self.jet_index + 1) % self.jets.len();
+
+            rock.blow(jet, &self.tower, height);
+
+            if height == 0 || self.tower.collision(&rock.bits, height - 1) {
+                self.tower.merge(&mut rock, height);
+                break;
+            }
+
+            height -= 1;
+        }
+
+        // it came to rest on the row below it, or on the floor
+        self.reach = self.reach.max(top + 1 - height);
+        self.dropped += 1;
+        let state = State {
+            rock_index: self.rock_index,
+            jet_index: self.jet_index,
+            top: self.tower.top_rows(self.reach),
+        };
+        Some((state, self.tower.get_height()))
+    }
+}
+
+fn problem1(input: &Input) -> usize {
+    let (_, height) = Drops::new(input).nth(2021).unwrap();
+    height
+}
+
+fn problem2(input: &Input) -> usize {
+    // the rocks and jets both go round in circles, so sooner or later the top of the tower will too
+    let heights = detect(Drops::new(input)).unwrap();
+    heights.value_at(1_000_000_000_000 - 1)
+}
+
+impl Display for Drops<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.tower.fmt(f)
+    }
+}
+
+/// How many rocks are worth watching: all 2022 for part 1, and for part 2 enough for the top of
+/// the tower to go round its cycle once
+fn drop_count(input: &Input, part: Part) -> usize {
+    match part {
+        Part::One => 2022,
+        Part::Two => {
+            let cycle = detect(Drops::new(input)).unwrap().cycle;
+            cycle.start + cycle.length
+        }
+    }
+}
+
+/// Drop rocks and watch the top of the tower
+fn render(input: &Input, part: Part) -> Vec<Frame> {
+    let count = drop_count(input, part);
+
+    let mut drops = Drops::new(input);
+    let mut recorder = Recorder::default();
+    let frame = |tower: &Tower| {
+        Top { tower, rows: 40 }.frame(|rock| if rock { [160, 150, 140] } else { [20, 20, 30] })
+    };
+    for _ in 1..count {
+        drops.next();
+        recorder.step(|| frame(&drops.tower));
+    }
+    drops.next();
+    recorder.finish(frame(&drops.tower))
+}
+
+#[cfg(test)]
+mod test {
+    use common::{cycle::detect, Solution};
+
+    use crate::{Day17, Drops};
+
+    common::examples!(Day17);
+    common::properties!(Day17, [1, 40, 10091]);
+
+    #[test]
+    fn cycles_match_dropping_every_rock() {
+        for jets in [">><>>><>>>", "<", "><", "<<<<>", ">>>>>>>>>>"] {
+            let input = Day17::parse(jets).unwrap();
+            let heights: Vec<usize> = Drops::new(&input).take(5000).map(|(_, h)| h).collect();
+            let cycle = detect(Drops::new(&input)).unwrap();
+            for n in [100, 2021, 4999] {
+                assert_eq!(cycle.value_at(n), heights[n], "{jets} after {n} rocks");
+            }
+        }
+    }
+
+    #[test]
+    fn one_sided_jets() {
+        // nothing ever lands on the left, so those columns only get deeper
+        let input = Day17::parse(">>>>>>>>>>").unwrap();
+        assert_eq!(Day17::part2(&input), 2_600_000_000_000);
+    }
+}
@@ -0,0 +1,200 @@
+//! Day 18: Boiling Boulders
+//!
+//! A droplet of lava cubes; part 1 counts its surface area, part 2 only the exterior surface.
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+
+use common::{
+    error::ParseError,
+    generate,
+    nom::{parse_lines, point3},
+    point::Point3,
+    Solution,
+};
+use ndarray::{prelude::*, OwnedRepr};
+use nom::combinator::map_res;
+use nom::error::context;
+use rand::{rngs::StdRng, Rng};
+
+/// Counts the faces of the droplet's cubes that nothing covers
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input<'a> = Input;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+fn print(cubes: &Input) -> String {
+    generate::lines(cubes.iter().map(|c| format!("{},{},{}", c.x, c.y, c.z)))
+}
+
+/// A droplet of `size` cubes (at most 5000), grown by wandering about the middle of the grid so
+/// it ends up lumpy with the odd air pocket. Every cube fits in 0 to 21 in each direction.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let count = size.clamp(1, 5000);
+    let mut seen = HashSet::new();
+    let mut cubes = Vec::with_capacity(count);
+    let mut at = [11_usize; 3];
+    while cubes.len() < count {
+        if seen.insert(at) {
+            cubes.push(format!("{},{},{}", at[0], at[1], at[2]));
+        }
+        let axis = rng.gen_range(0..3);
+        at[axis] = if rng.gen() {
+            (at[axis] + 1).min(21)
+        } else {
+            at[axis].saturating_sub(1)
+        };
+    }
+    generate::lines(cubes)
+}
+
+/// Where each cube of the droplet is
+pub type Input = Vec<Point3<usize>>;
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_lines(
+        input,
+        context(
+            "cube",
+            map_res(point3, |p| {
+                Ok::<_, std::num::TryFromIntError>(Point3::new(
+                    p.x.try_into()?,
+                    p.y.try_into()?,
+                    p.z.try_into()?,
+                ))
+            }),
+        ),
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Air,
+    Lava,
+    Vacuum,
+}
+
+struct Grid {
+    grid: ArrayBase<OwnedRepr<Cell>, Dim<[usize; 3]>>,
+}
+
+impl From<&Vec<Point3<usize>>> for Grid {
+    fn from(value: &Vec<Point3<usize>>) -> Self {
+        Grid::from_points(value)
+    }
+}
+
+impl Grid {
+    const SIZE: usize = 24;
+    fn from_points(points: &[Point3<usize>]) -> Grid {
+        let mut grid = Array3::<Cell>::from_elem((Self::SIZE, Self::SIZE, Self::SIZE), Cell::Air);
+        for &p in points {
+            // push them up by one so we can get the edges correctly
+            grid[<[usize; 3]>::from(p + Point3::new(1, 1, 1))] = Cell::Lava;
+        }
+
+        Grid { grid }
+    }
+
+    // leave a ring of air around the grid
+    fn set_vacuum(&mut self) {
+        for x in 1..Self::SIZE {
+            for y in 1..Self::SIZE {
+                for z in 1..Self::SIZE {
+                    if self.grid[[x, y, z]] == Cell::Air {
+                        self.grid[[x, y, z]] = Cell::Vacuum;
+                    }
+                }
+            }
+        }
+    }
+    fn get_surface_area(&self) -> usize {
+        let mut total_empty = 0;
+        for x in 1..Self::SIZE {
+            for y in 1..Self::SIZE {
+                for z in 1..Self::SIZE {
+                    if self.grid[[x, y, z]] == Cell::Lava {
+                        // this is a voxel, check all its neighbors
+                        total_empty += Point3::new(x, y, z)
+                            .adjacent()
+                            .into_iter()
+                            .filter(|&p| self.grid[<[usize; 3]>::from(p)] == Cell::Air)
+                            .count();
+                    }
+                }
+            }
+        }
+        total_empty
+    }
+
+    fn flood_fill(&mut self) {
+        self.set_vacuum();
+        let mut updated = true;
+        while updated {
+            updated = false;
+            for x in 1..Self::SIZE - 1 {
+                for y in 1..Self::SIZE - 1 {
+                    for z in 1..Self::SIZE - 1 {
+                        // check if this cell is a vacuum and any of its neighbors are air
+                        if self.grid[[x, y, z]] == Cell::Vacuum {
+                            let any_air_neighbors = Point3::new(x, y, z)
+                                .adjacent()
+                                .into_iter()
+                                .any(|p| self.grid[<[usize; 3]>::from(p)] == Cell::Air);
+
+                            if any_air_neighbors {
+                                updated = true;
+                                self.grid[[x, y, z]] = Cell::Air;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn problem1(input: &Input) -> usize {
+    let grid: Grid = input.into();
+    grid.get_surface_area()
+}
+
+fn problem2(input: &Input) -> usize {
+    let mut grid: Grid = input.into();
+    grid.flood_fill();
+    grid.get_surface_area()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day18;
+
+    common::examples!(Day18);
+    common::properties!(Day18, [1, 100, 5000]);
+}
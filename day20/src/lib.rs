@@ -0,0 +1,122 @@
+//! Day 20: Grove Positioning System
+//!
+//! Mixes a list of numbers by moving each by its own value and sums the grove coordinates,
+//! the second time with a decryption key and ten rounds of mixing.
+
+#![warn(missing_docs)]
+
+use common::{error::ParseError, generate, nom::parse_lines, Solution};
+use nom::{character::complete::i64 as nom_i64, error::context};
+use rand::{rngs::StdRng, Rng};
+
+/// Mixes the encrypted file and reads off the grove coordinates
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input<'a> = Input;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+/// The encrypted file's numbers, in their original order
+pub type Input = Vec<i64>;
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_lines(input, context("number", nom_i64))
+}
+
+/// `size` numbers (at least two, or there's nowhere to move them), exactly one of them zero
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let count = size.max(2);
+    let zero = rng.gen_range(0..count);
+    generate::lines((0..count).map(|n| {
+        if n == zero {
+            0
+        } else {
+            // anything but zero
+            rng.gen_range(1..=10_000) * if rng.gen() { 1 } else { -1 }
+        }
+    }))
+}
+
+fn print(numbers: &Input) -> String {
+    generate::lines(numbers)
+}
+
+fn mix(numbers: &Input, cycles: u64) -> Vec<i64> {
+    // transform to pair of (idx, num)
+    let mut numbers: Vec<(usize, &i64)> = numbers.iter().enumerate().collect();
+    let size = numbers.len() as i64;
+
+    for _cycle in 0..cycles {
+        for og_index_to_find in 0..numbers.len() {
+            // find where the index originally was
+            let current_index = numbers
+                .iter()
+                .position(|&(og_index, _x)| og_index == og_index_to_find)
+                .unwrap();
+
+            // mod math our way around the list to get to the new insertion index
+            let (_og_index, num) = numbers[current_index];
+            let new_index = (current_index as i64 + num).rem_euclid(size - 1) as usize;
+
+            // remove and re-insert the value
+            let number = numbers.remove(current_index);
+            numbers.insert(new_index, number);
+        }
+    }
+
+    // transform back to just numbers
+    numbers.iter().map(|&(_i, &x)| x).collect()
+}
+
+fn get_coordinates(numbers: &[i64]) -> i64 {
+    let length = numbers.len();
+    // find the 1, 2, and 3000th numbers from the current position of the 0 element
+    let start = numbers.iter().position(|&x| x == 0).unwrap();
+    (1..=3)
+        .map(|x| numbers[(start + (x * 1000)) % length])
+        .sum()
+}
+
+fn problem1(input: &Input) -> i64 {
+    let result = mix(input, 1);
+    get_coordinates(&result)
+}
+
+fn problem2(input: &Input) -> i64 {
+    let decryption_key = 811_589_153;
+    let multiplied: Vec<i64> = input.iter().map(|x| x * decryption_key).collect();
+    let result = mix(&multiplied, 10);
+    get_coordinates(&result)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day20;
+
+    common::examples!(Day20);
+    common::properties!(Day20, [1, 20, 5000]);
+}
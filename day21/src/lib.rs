@@ -0,0 +1,358 @@
+//! Day 21: Monkey Math
+//!
+//! Monkeys yelling numbers or [MonkeyValue] operations on other monkeys' numbers; part 1 works
+//! out root's number, part 2 what we have to yell for root's two sides to match.
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+
+use common::{
+    error::ParseError,
+    generate,
+    nom::{parse_lines, IResult},
+    Solution,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, i64 as nom_i64},
+    combinator::map,
+    error::context,
+    sequence::separated_pair,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
+
+/// Works out what the monkeys yell, from the leaves up to root
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input<'a> = Input<'a>;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+/// What a monkey yells, either a number or a sum over two other monkeys' numbers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonkeyValue<'a> {
+    /// A number, or `None` for the one we have to work out ourselves in part 2
+    Literal(Option<i64>),
+    /// `a + b`
+    Plus(&'a str, &'a str),
+    /// `a - b`
+    Minus(&'a str, &'a str),
+    /// `a * b`
+    Times(&'a str, &'a str),
+    /// `a / b`
+    Divides(&'a str, &'a str),
+    /// Root's job in part 2, checking that both sides are equal
+    Equals(&'a str, &'a str),
+}
+
+impl<'a> MonkeyValue<'a> {
+    fn get_dependencies(&self) -> Option<(&'a str, &'a str)> {
+        match self {
+            MonkeyValue::Equals(l, r) => Some((*l, *r)),
+            MonkeyValue::Plus(l, r) => Some((*l, *r)),
+            MonkeyValue::Minus(l, r) => Some((*l, *r)),
+            MonkeyValue::Times(l, r) => Some((*l, *r)),
+            MonkeyValue::Divides(l, r) => Some((*l, *r)),
+            MonkeyValue::Literal(_) => None,
+        }
+    }
+}
+
+/// Each monkey's name and what it yells
+pub type Input<'a> = Vec<(&'a str, MonkeyValue<'a>)>;
+
+fn monkey_value(input: &str) -> IResult<&str, MonkeyValue<'_>> {
+    alt((
+        map(nom_i64, |x| MonkeyValue::Literal(Some(x))),
+        map(separated_pair(alpha1, tag(" + "), alpha1), |(lhs, rhs)| {
+            MonkeyValue::Plus(lhs, rhs)
+        }),
+        map(separated_pair(alpha1, tag(" - "), alpha1), |(lhs, rhs)| {
+            MonkeyValue::Minus(lhs, rhs)
+        }),
+        map(separated_pair(alpha1, tag(" * "), alpha1), |(lhs, rhs)| {
+            MonkeyValue::Times(lhs, rhs)
+        }),
+        map(separated_pair(alpha1, tag(" / "), alpha1), |(lhs, rhs)| {
+            MonkeyValue::Divides(lhs, rhs)
+        }),
+    ))(input)
+}
+
+fn parse(input: &str) -> Result<Input<'_>, ParseError> {
+    parse_lines(
+        input,
+        context("monkey", separated_pair(alpha1, tag(": "), monkey_value)),
+    )
+}
+
+/// A chain of `size` monkeys (up to 200) leading from humn up to root, each doing something to
+/// the number below it with a number worked out by monkeys off to the side. Every step can be
+/// undone exactly when humn shouts the part 2 answer, and the last one before root is picked so
+/// that both sides of root agree then.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    type Names<'n> = &'n mut dyn Iterator<Item = String>;
+    type Jobs = Vec<(String, String)>;
+
+    fn literal(names: Names, jobs: &mut Jobs, value: i64) -> String {
+        let name = names.next().unwrap();
+        jobs.push((name.clone(), value.to_string()));
+        name
+    }
+
+    // a monkey with a known number, sometimes worked out from two others, never dividing unevenly
+    fn constant(rng: &mut StdRng, names: Names, jobs: &mut Jobs) -> (String, i64) {
+        let (job, value) = match rng.gen_range(0..5) {
+            0 | 1 => return leaf(rng, names, jobs),
+            2 => {
+                let (a, b) = (rng.gen_range(1..=20), rng.gen_range(1..=20));
+                let job = format!("{} + {}", literal(names, jobs, a), literal(names, jobs, b));
+                (job, a + b)
+            }
+            3 => {
+                let (a, b) = (rng.gen_range(1..=20), rng.gen_range(1..=20));
+                let job = format!("{} * {}", literal(names, jobs, a), literal(names, jobs, b));
+                (job, a * b)
+            }
+            _ => {
+                let (b, q) = (rng.gen_range(1..=5), rng.gen_range(1..=10));
+                let job = format!(
+                    "{} / {}",
+                    literal(names, jobs, b * q),
+                    literal(names, jobs, b)
+                );
+                (job, q)
+            }
+        };
+        let name = names.next().unwrap();
+        jobs.push((name.clone(), job));
+        (name, value)
+    }
+
+    fn leaf(rng: &mut StdRng, names: Names, jobs: &mut Jobs) -> (String, i64) {
+        let value = rng.gen_range(1..=20);
+        (literal(names, jobs, value), value)
+    }
+
+    // either way round, for the operations where that doesn't matter
+    fn either(rng: &mut StdRng, a: &str, op: char, b: &str) -> String {
+        if rng.gen() {
+            format!("{a} {op} {b}")
+        } else {
+            format!("{b} {op} {a}")
+        }
+    }
+
+    let steps = size.clamp(1, 200);
+    let mut names = generate::names(rng, 4 * steps + 16, 4, "abcdefghijklmnopqrstuvwxyz")
+        .into_iter()
+        .filter(|n| n != "root" && n != "humn");
+    let names: Names = &mut names;
+    let mut jobs: Jobs = Vec::new();
+
+    // what humn shouts in part 1, and what they need to shout in part 2
+    let shouted: i64 = rng.gen_range(1..=5_000);
+    let answer: i64 = rng.gen_range(100..=10_000);
+    jobs.push(("humn".to_owned(), shouted.to_string()));
+
+    let (mut chain, mut at_shouted, mut at_answer) = ("humn".to_owned(), shouted, answer);
+    for _ in 0..steps {
+        let small = at_shouted.abs().max(at_answer.abs()) < 1_000_000_000_000;
+        let k = rng.gen_range(2..=5);
+        let job = match rng.gen_range(0..4) {
+            0 if small => {
+                at_shouted *= k;
+                at_answer *= k;
+                either(rng, &chain, '*', &literal(names, &mut jobs, k))
+            }
+            1 if at_answer % k == 0 => {
+                at_shouted /= k;
+                at_answer /= k;
+                format!("{chain} / {}", literal(names, &mut jobs, k))
+            }
+            2 => {
+                let (other, k) = constant(rng, names, &mut jobs);
+                if rng.gen() {
+                    at_shouted -= k;
+                    at_answer -= k;
+                    format!("{chain} - {other}")
+                } else {
+                    at_shouted = k - at_shouted;
+                    at_answer = k - at_answer;
+                    format!("{other} - {chain}")
+                }
+            }
+            _ => {
+                let (other, k) = constant(rng, names, &mut jobs);
+                at_shouted += k;
+                at_answer += k;
+                either(rng, &chain, '+', &other)
+            }
+        };
+        chain = names.next().unwrap();
+        jobs.push((chain.clone(), job));
+    }
+
+    // make up the difference so root's two sides match when humn shouts the answer
+    let (other, target) = constant(rng, names, &mut jobs);
+    let difference = target - at_answer;
+    let job = if difference >= 0 {
+        format!("{chain} + {}", literal(names, &mut jobs, difference))
+    } else {
+        format!("{chain} - {}", literal(names, &mut jobs, -difference))
+    };
+    chain = names.next().unwrap();
+    jobs.push((chain.clone(), job));
+    jobs.push(("root".to_owned(), either(rng, &chain, '+', &other)));
+
+    jobs.shuffle(rng);
+    generate::lines(jobs.into_iter().map(|(name, job)| format!("{name}: {job}")))
+}
+
+fn print(monkeys: &Input) -> String {
+    generate::lines(monkeys.iter().map(|(name, job)| {
+        let job = match job {
+            MonkeyValue::Literal(Some(x)) => x.to_string(),
+            MonkeyValue::Plus(l, r) => format!("{l} + {r}"),
+            MonkeyValue::Minus(l, r) => format!("{l} - {r}"),
+            MonkeyValue::Times(l, r) => format!("{l} * {r}"),
+            MonkeyValue::Divides(l, r) => format!("{l} / {r}"),
+            MonkeyValue::Literal(None) | MonkeyValue::Equals(_, _) => {
+                unreachable!("only part 2 makes monkeys like {name}")
+            }
+        };
+        format!("{name}: {job}")
+    }))
+}
+
+struct Equation<'a> {
+    map: HashMap<&'a str, MonkeyValue<'a>>,
+}
+
+impl<'a> Equation<'a> {
+    fn new(input: &'a Input) -> Equation<'a> {
+        let map = input.iter().copied().collect();
+        Equation { map }
+    }
+
+    fn evaluate(&self, current: &str) -> Option<i64> {
+        let current = self.map[current];
+
+        if let MonkeyValue::Literal(x) = current {
+            return x;
+        }
+
+        match current {
+            MonkeyValue::Plus(l, r) => self
+                .evaluate(l)
+                .and_then(|l| self.evaluate(r).map(|r| l + r)),
+            MonkeyValue::Minus(l, r) => self
+                .evaluate(l)
+                .and_then(|l| self.evaluate(r).map(|r| l - r)),
+            MonkeyValue::Times(l, r) => self
+                .evaluate(l)
+                .and_then(|l| self.evaluate(r).map(|r| l * r)),
+            MonkeyValue::Divides(l, r) => self
+                .evaluate(l)
+                .and_then(|l| self.evaluate(r).map(|r| l / r)),
+            _ => unreachable!("Something went wrong"),
+        }
+    }
+
+    fn solve(&self, id: &str, value_to_solve: Option<i64>) -> Option<i64> {
+        // We're here, so just return the value to solve, we've already solved it
+        if id == "humn" {
+            return value_to_solve;
+        }
+
+        let monkey = self.map[id];
+        let Some((l, r)) = monkey.get_dependencies() else {
+            panic!();
+        };
+
+        // one of these will be none and the other will be a solved monkey
+        let left = self.evaluate(l);
+        let right = self.evaluate(r);
+
+        let (unknown_id, known) = match (left, right) {
+            (None, Some(x)) => (l, x),
+            (Some(x), None) => (r, x),
+            _ => panic!("No idea what went wrong here"),
+        };
+
+        // this is the starting point, so go ahead and recurse on the unknown side of the equation
+        if id == "root" {
+            return self.solve(unknown_id, Some(known));
+        }
+
+        let value_to_solve = value_to_solve.map(|value| match monkey {
+            MonkeyValue::Plus(_, _) => value - known,
+            MonkeyValue::Minus(_, _) if l == unknown_id => value + known,
+            MonkeyValue::Minus(_, _) if r == unknown_id => known - value,
+            MonkeyValue::Divides(_, _) if l == unknown_id => value * known,
+            MonkeyValue::Divides(_, _) if r == unknown_id => known / value,
+            MonkeyValue::Times(_, _) => value / known,
+
+            _ => unreachable!(),
+        });
+
+        self.solve(unknown_id, value_to_solve)
+    }
+}
+
+fn problem1(input: &Input) -> i64 {
+    let equation = Equation::new(input);
+    equation.evaluate("root").unwrap()
+}
+
+fn problem2(input: &Input) -> i64 {
+    let mut equation = Equation::new(input);
+
+    // set root to an Equals Monkey
+    let root = equation.map.get_mut("root").unwrap();
+    let Some((l, r)) = root.get_dependencies() else {
+        panic!()
+    };
+    *root = MonkeyValue::Equals(l, r);
+
+    // set humn to None so the whole tree and any tree that contains it will eval to None
+    let humn = equation.map.get_mut("humn").unwrap();
+    *humn = MonkeyValue::Literal(None);
+
+    // keep solving "the other side" of the tree so we can get a literal to factor out of the other side of the equality check
+    equation.solve("root", None).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day21;
+
+    common::examples!(Day21);
+    common::properties!(Day21, [1, 10, 200]);
+}
@@ -0,0 +1,406 @@
+//! Day 22: Monkey Map
+//!
+//! A map of open and solid [Space]s and a path of [Instruction]s; part 1 wraps around the flat
+//! map, part 2 folds it into a cube.
+
+#![warn(missing_docs)]
+
+use common::{error::ParseError, point::Dir4, Solution};
+use ndarray::prelude::*;
+
+use crate::parsing::parse;
+
+mod parsing;
+use rand::{rngs::StdRng, Rng};
+
+/// Follows the path around the board, flat and then folded
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Input<'a> = Input;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+/// The board, and the path to follow on it
+pub type Input = (Array2<Space>, Vec<Instruction>);
+
+/// A tile of the map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Space {
+    /// Off the edge of the map, a space in the input
+    Void,
+    /// `.`, open to walk on
+    Empty,
+    /// `#`, which stops us walking any further
+    Wall,
+}
+
+/// One step of the path to follow
+#[derive(Debug, PartialEq, Eq)]
+pub enum Instruction {
+    /// Walk forward up to this many tiles
+    Walk(u32),
+    /// `L`, turn 90 degrees anticlockwise
+    TurnLeft,
+    /// `R`, turn 90 degrees clockwise
+    TurnRight,
+}
+
+/// A map folded the same way as the real puzzle's, since part 2 only knows how to fold that one,
+/// with walls scattered across it, and a path of `size` walks
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut map = String::new();
+    for y in 0..200 {
+        let columns = match y {
+            0..=49 => 50..150,
+            50..=99 => 50..100,
+            100..=149 => 0..100,
+            _ => 0..50,
+        };
+        for x in 0..columns.end {
+            map.push(if !columns.contains(&x) {
+                ' '
+            } else if (x, y) != (50, 0) && rng.gen_ratio(1, 12) {
+                '#'
+            } else {
+                // including where we start
+                '.'
+            });
+        }
+        map.push('\n');
+    }
+
+    let mut path = rng.gen_range(1..=50).to_string();
+    for _ in 1..size.max(1) {
+        path.push(if rng.gen() { 'L' } else { 'R' });
+        path += &rng.gen_range(1..=50).to_string();
+    }
+    format!("{map}\n{path}\n")
+}
+
+fn print((grid, path): &Input) -> String {
+    let mut text = String::new();
+    for row in grid.rows() {
+        text.extend(row.iter().map(|space| match space {
+            Space::Void => ' ',
+            Space::Empty => '.',
+            Space::Wall => '#',
+        }));
+        text.push('\n');
+    }
+    text.push('\n');
+    for instruction in path {
+        match instruction {
+            Instruction::Walk(steps) => text += &steps.to_string(),
+            Instruction::TurnLeft => text.push('L'),
+            Instruction::TurnRight => text.push('R'),
+        }
+    }
+    text + "\n"
+}
+
+#[allow(dead_code)]
+fn print_map(grid: &Array2<Space>, player_position: &Position) {
+    for (y, row) in grid.outer_iter().enumerate() {
+        for (x, space) in row.iter().enumerate() {
+            if [y, x] == player_position.coords {
+                print!("{}", player_position.heading.arrow());
+                continue;
+            }
+            match space {
+                Space::Void => print!(" "),
+                Space::Empty => print!("."),
+                Space::Wall => print!("#"),
+            }
+        }
+        println!();
+    }
+    println!("==================");
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Position {
+    coords: [usize; 2],
+    heading: Dir4,
+}
+
+impl Position {
+    fn get_start(grid: &Array2<Space>) -> Position {
+        // find the starting position as the first Empty square on the first row of the board
+        Position {
+            coords: [
+                0,
+                grid.row(0).iter().position(|&x| x == Space::Empty).unwrap(),
+            ],
+            heading: Dir4::Right,
+        }
+    }
+
+    fn rotate(&mut self, instruction: &Instruction) {
+        self.heading = match instruction {
+            Instruction::TurnLeft => self.heading.rotate_left(),
+            Instruction::TurnRight => self.heading.rotate_right(),
+            Instruction::Walk(_) => self.heading,
+        };
+    }
+
+    fn get_password(&self) -> u32 {
+        // The final password is the sum of 1000 times the row, 4 times the column, and the facing.
+        let first = 1000 * (self.coords[0] + 1) as u32;
+        let second = 4 * (self.coords[1] + 1) as u32;
+        let third = match self.heading {
+            Dir4::Right => 0,
+            Dir4::Down => 1,
+            Dir4::Left => 2,
+            Dir4::Up => 3,
+        };
+
+        first + second + third
+    }
+
+    fn walk(&mut self, steps: u32, grid: &Array2<Space>, void_treatment: VoidTreatment) {
+        // get the correct axis to look at and slice the array on that axis
+        let (axis, idx, rev) = match self.heading {
+            Dir4::Up => (Axis(1), 0, true),
+            Dir4::Down => (Axis(1), 0, false),
+            Dir4::Left => (Axis(0), 1, true),
+            Dir4::Right => (Axis(0), 1, false),
+        };
+
+        let slice = grid.index_axis(axis, self.coords[axis.0]);
+
+        let mut s = 0u32;
+        while s < steps {
+            // look at the next space
+            let step = if rev { slice.len() - 1 } else { 1 };
+            let next = (self.coords[idx] + step) % slice.len();
+
+            match slice.get(next) {
+                Some(Space::Wall) => break,
+                Some(Space::Empty) => {
+                    s += 1;
+                    self.coords[idx] = next;
+                }
+                Some(Space::Void) => {
+                    match void_treatment {
+                        VoidTreatment::TwoD => {
+                            let next_non_void = if rev {
+                                slice
+                                    .iter()
+                                    .rev()
+                                    .cycle()
+                                    .find(|&&x| x != Space::Void)
+                                    .unwrap()
+                            } else {
+                                slice.iter().cycle().find(|&&x| x != Space::Void).unwrap()
+                            };
+
+                            if *next_non_void == Space::Wall {
+                                break;
+                            }
+
+                            // is the next non-void space a wall?
+                            self.coords[idx] = next;
+                        }
+                        VoidTreatment::ThreeD => {
+                            /* when we hit a void on the map, but the map is in 3d mode, we need to translate
+                            ourselves off this cube edge and onto another, we also still need to check that
+                            the next space isn't a wall */
+
+                            // translate our coords and heading
+                            let (next_row, next_col, next_heading) = self.translate_to_3d();
+
+                            if grid[[next_row, next_col]] == Space::Wall {
+                                break;
+                            }
+
+                            self.coords = [next_row, next_col];
+                            self.heading = next_heading;
+
+                            // complete the rest of the walk
+                            self.walk(steps - s - 1, grid, void_treatment);
+                            return;
+                        }
+                    }
+                }
+                None => unreachable!(),
+            }
+        }
+    }
+
+    fn get_face(&self) -> usize {
+        let row = self.coords[0];
+        let col = self.coords[1];
+
+        match (row, col) {
+            (0..=49, 50..=99) => 1,
+            (0..=49, 100..=149) => 2,
+            (50..=99, 50..=99) => 3,
+            (100..=149, 50..=99) => 4,
+            (100..=149, 0..=49) => 5,
+            (150..=199, 0..=49) => 6,
+
+            _ => panic!(),
+        }
+    }
+
+    fn translate_to_3d(&mut self) -> (usize, usize, Dir4) {
+        let row = self.coords[0];
+        let col = self.coords[1];
+
+        let heading = self.heading;
+        let face = self.get_face();
+
+        match (face, heading, row, col) {
+            (1, Dir4::Up, 0, _) => (150 + (col - 50), 0, Dir4::Right),
+            (1, Dir4::Left, _, 50) => (149 - row, 0, Dir4::Right),
+
+            (2, Dir4::Up, 0, _) => (199, col - 100, Dir4::Up),
+            (2, Dir4::Right, _, 149) => (149 - row, 99, Dir4::Left),
+            (2, Dir4::Down, 49, _) => (50 + (col - 100), 99, Dir4::Left),
+
+            (3, Dir4::Left, _, 50) => (100, row - 50, Dir4::Down),
+            (3, Dir4::Right, _, 99) => (49, 100 + (row - 50), Dir4::Up),
+
+            (4, Dir4::Right, _, 99) => (49 - (row - 100), 149, Dir4::Left),
+            (4, Dir4::Down, 149, _) => (150 + (col - 50), 49, Dir4::Left),
+
+            (5, Dir4::Left, _, 0) => (49 - (row - 100), 50, Dir4::Right),
+            (5, Dir4::Up, 100, _) => (50 + col, 50, Dir4::Right),
+
+            (6, Dir4::Left, _, 0) => (0, 50 + (row - 150), Dir4::Down),
+            (6, Dir4::Down, 199, _) => (0, 100 + col, Dir4::Down),
+            (6, Dir4::Right, _, 49) => (149, 50 + (row - 150), Dir4::Up),
+
+            _ => panic!(),
+        }
+    }
+}
+
+enum VoidTreatment {
+    TwoD,
+    ThreeD,
+}
+
+fn problem1(input: &Input) -> u32 {
+    let (grid, moves) = input;
+    let mut player_position = Position::get_start(grid);
+
+    for x in moves.iter() {
+        match x {
+            Instruction::TurnLeft | Instruction::TurnRight => {
+                player_position.rotate(x);
+            }
+            Instruction::Walk(steps) => player_position.walk(*steps, grid, VoidTreatment::TwoD),
+        }
+    }
+
+    player_position.get_password()
+}
+
+fn problem2(input: &Input) -> u32 {
+    let (grid, moves) = input;
+    let mut player_position = Position::get_start(grid);
+
+    for x in moves.iter() {
+        match x {
+            Instruction::TurnLeft | Instruction::TurnRight => {
+                player_position.rotate(x);
+            }
+            Instruction::Walk(steps) => player_position.walk(*steps, grid, VoidTreatment::ThreeD),
+        }
+    }
+
+    player_position.get_password()
+}
+
+#[cfg(test)]
+mod test {
+    use common::test::get_raw_input;
+
+    use common::point::Dir4;
+
+    use crate::{parse, parsing::parse_grid, problem2, Day22, Position};
+
+    common::examples!(Day22);
+    common::properties!(Day22, [1, 10, 4000]);
+
+    #[test]
+    #[ignore]
+    fn second() {
+        let input = get_raw_input();
+        let input = parse(&input).unwrap();
+        let result = problem2(&input);
+        assert_eq!(result, 5031)
+    }
+    #[test]
+    fn walk_up() {
+        let map = ". 
+# 
+..
+.#
+..";
+
+        let grid = parse_grid(map).unwrap().1;
+
+        let mut p = Position {
+            coords: [0, 0],
+            heading: Dir4::Up,
+        };
+
+        // can walk over the top of the map
+        p.walk(5, &grid, crate::VoidTreatment::TwoD);
+        assert_eq!(p.coords, [2, 0]);
+
+        // now move over one
+        p.coords = [2, 1];
+
+        // can walk over the top, over a void, and hit a wall
+        p.walk(5, &grid, crate::VoidTreatment::TwoD);
+        assert_eq!(p.coords, [4, 1]);
+    }
+
+    #[test]
+    fn walk_left() {
+        let map = "  .#.
+  ..#";
+
+        let grid = parse_grid(map).unwrap().1;
+
+        let mut p = Position {
+            coords: [0, 2],
+            heading: Dir4::Left,
+        };
+
+        // can walk over void left
+        p.walk(3, &grid, crate::VoidTreatment::TwoD);
+        assert_eq!(p.coords, [0, 4]);
+
+        p.coords = [1, 3];
+        p.walk(10, &grid, crate::VoidTreatment::TwoD);
+        assert_eq!(p.coords, [1, 2]);
+    }
+}
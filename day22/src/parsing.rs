@@ -1,48 +1,55 @@
+use common::{
+    error::ParseError,
+    nom::{blank_line, grid, parse_complete, IResult},
+};
 use ndarray::Array2;
 use nom::{
     branch::alt,
-    bytes::complete::tag,
-    character::complete::{char, newline, u32 as nom_u32},
+    character::complete::{char, u32 as nom_u32},
     combinator::map,
-    multi::{many1, separated_list1},
+    error::context,
+    multi::many1,
     sequence::separated_pair,
-    IResult,
 };
 
 use crate::{Input, Instruction, Space};
 
 pub(crate) fn parse_grid(input: &str) -> IResult<&str, Array2<Space>> {
-    map(
-        separated_list1(
-            newline,
-            many1(alt((
-                map(char(' '), |_| Space::Void),
-                map(char('.'), |_| Space::Empty),
-                map(char('#'), |_| Space::Wall),
-            ))),
+    context(
+        "grid",
+        map(
+            grid(|c| match c {
+                ' ' => Some(Space::Void),
+                '.' => Some(Space::Empty),
+                '#' => Some(Space::Wall),
+                _ => None,
+            }),
+            to_array,
         ),
-        to_array,
     )(input)
 }
 
 pub(crate) fn parse_instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
-    many1(alt((
-        map(nom_u32, Instruction::Walk),
-        map(char('L'), |_| Instruction::TurnLeft),
-        map(char('R'), |_| Instruction::TurnRight),
-    )))(input)
+    context(
+        "instructions",
+        many1(alt((
+            map(nom_u32, Instruction::Walk),
+            map(char('L'), |_| Instruction::TurnLeft),
+            map(char('R'), |_| Instruction::TurnRight),
+        ))),
+    )(input)
 }
 
-pub(crate) fn parse(input: &str) -> Input {
-    let result: IResult<&str, Input> =
-        separated_pair(parse_grid, tag("\n\n"), parse_instructions)(input);
-
-    result.unwrap().1
+pub(crate) fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_complete(
+        input,
+        separated_pair(parse_grid, blank_line, parse_instructions),
+    )
 }
 
 fn to_array(grid: Vec<Vec<Space>>) -> Array2<Space> {
     let height = grid.len();
-    let width = grid.iter().map(|x| x.len()).max().unwrap();
+    let width = grid.iter().map(|x| x.len()).max().unwrap_or(0);
 
     let mut new_grid = Array2::from_elem((height, width), Space::Void);
 
@@ -0,0 +1,265 @@
+//! Day 23: Unstable Diffusion
+//!
+//! Elves spreading out over a grid; the empty ground after ten rounds, then the first round
+//! nobody moves.
+
+#![warn(missing_docs)]
+
+use std::{collections::HashMap, fmt::Display};
+
+use common::{
+    animate::{Playback, Steps},
+    error::ParseError,
+    generate,
+    nom::{grid, parse_complete},
+    point::Dir8,
+    render::{Frame, Recorder, Render},
+    sparse::{Bounds, Point, SparseGrid},
+    Part, Solution,
+};
+use nom::error::context;
+use rand::{rngs::StdRng, Rng};
+
+/// Spreads the elves out a round at a time
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Input<'a> = Input;
+    type Part1 = u64;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(&mut input.clone())
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(&mut input.clone())
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+
+    fn render(input: &Self::Input<'_>, part: Part) -> Option<Vec<Frame>> {
+        let rounds = match part {
+            Part::One => Some(10),
+            Part::Two => None,
+        };
+        Some(render(&mut input.clone(), rounds))
+    }
+
+    fn animate<'a>(input: &'a Self::Input<'_>, part: Part) -> Option<Box<dyn Playback + 'a>> {
+        let rounds = match part {
+            Part::One => 10,
+            Part::Two => usize::MAX,
+        };
+        Some(Box::new(Steps::new(
+            || Grove {
+                elves: input.clone(),
+                round: 0,
+            },
+            move |grove| {
+                if grove.round == rounds || !play_round(&mut grove.elves, grove.round) {
+                    return false;
+                }
+                grove.round += 1;
+                true
+            },
+        )))
+    }
+}
+
+/// The elves' positions, with nothing stored at each
+pub type Input = SparseGrid<()>;
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    let rows = parse_complete(
+        input,
+        context(
+            "grove",
+            grid(|c| match c {
+                '#' => Some(true),
+                '.' => Some(false),
+                _ => None,
+            }),
+        ),
+    )?;
+
+    Ok(rows
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter_map(move |(x, elf)| elf.then_some((Point::new(x as i64, y as i64), ())))
+        })
+        .collect())
+}
+
+/// A square grove `size` tiles across, about half of it elves, with at least the one in the
+/// middle
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let side = size.max(1);
+    generate::grid(side, side, |x, y| {
+        if (x, y) == (side / 2, side / 2) || rng.gen() {
+            '#'
+        } else {
+            '.'
+        }
+    })
+}
+
+/// The grove from the top left corner the parser counts from, so the elves stay where they were
+fn print(elves: &Input) -> String {
+    let Some(bounds) = elves.bounds() else {
+        return ".\n".to_owned();
+    };
+    let from_corner = Bounds {
+        min_x: 0,
+        min_y: 0,
+        ..bounds
+    };
+    elves.render_within(from_corner, |_, elf| if elf.is_some() { '#' } else { '.' })
+}
+
+#[derive(Debug)]
+struct Proposal {
+    current: Point,
+    proposed: Point,
+}
+
+fn get_proposal(round: usize, input: &Input, elf: Point) -> Option<Proposal> {
+    // first half, let's check all our neighbors
+    let occupied = |dir: Dir8| input.contains(elf + dir);
+    if !Dir8::ALL.into_iter().any(occupied) {
+        return None;
+    }
+
+    // if we do have neighbors, we need to propose a direction where it and both the diagonals
+    // either side of it are free
+    let proposals = [Dir8::Up, Dir8::Down, Dir8::Left, Dir8::Right];
+
+    (0..4)
+        .map(|i| proposals[(round + i) % 4])
+        .find(|&dir| {
+            ![dir.rotate_left(), dir, dir.rotate_right()]
+                .into_iter()
+                .any(occupied)
+        })
+        .map(|dir| Proposal {
+            current: elf,
+            proposed: elf + dir,
+        })
+}
+
+fn get_proposals(input: &Input, round: usize) -> HashMap<Point, Vec<Point>> {
+    // get the proposals
+    let proposals: Vec<Proposal> = input
+        .points()
+        .filter_map(|e| get_proposal(round, input, e))
+        .collect();
+
+    // put them into a map so we can group them
+    let mut map: HashMap<Point, Vec<Point>> = HashMap::new();
+    for p in &proposals {
+        map.entry(p.proposed)
+            .and_modify(|v| v.push(p.current))
+            .or_insert_with(|| vec![p.current]);
+    }
+
+    map
+}
+
+/// The elves after so many rounds
+struct Grove {
+    elves: Input,
+    round: usize,
+}
+
+impl Display for Grove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(bounds) = self.elves.occupied_bounds() else {
+            return Ok(());
+        };
+
+        let rendered = self
+            .elves
+            .render_within(bounds, |_, elf| if elf.is_some() { '#' } else { '.' });
+        write!(f, "{rendered}")
+    }
+}
+
+/// Play out a round, returning whether anybody moved
+fn play_round(input: &mut Input, round: usize) -> bool {
+    let proposals = get_proposals(input, round);
+
+    // now move them around
+    proposals
+        .iter()
+        .filter(|(_, current)| current.len() == 1)
+        .for_each(|(target, current)| {
+            input.remove(current[0]);
+            input.insert(*target, ());
+        });
+
+    !proposals.is_empty()
+}
+
+fn problem1(input: &mut Input) -> u64 {
+    for round in 0..=9 {
+        // no point in going further, nobody is moving
+        if !play_round(input, round) {
+            break;
+        }
+    }
+
+    // the elves have spread out, so only the ones still standing somewhere count
+    let bounds = input.occupied_bounds().unwrap();
+    bounds.area() - (input.len() as u64)
+}
+
+fn problem2(input: &mut Input) -> usize {
+    (0..).find(|&round| !play_round(input, round)).unwrap() + 1
+}
+
+/// Watch the elves spread out, for so many rounds or until they stop moving
+fn render(input: &mut Input, rounds: Option<usize>) -> Vec<Frame> {
+    let mut recorder = Recorder::default();
+    for round in 0..rounds.unwrap_or(usize::MAX) {
+        recorder.step(|| input.clone());
+        if !play_round(input, round) {
+            break;
+        }
+    }
+    let snapshots = recorder.finish(input.clone());
+
+    // every frame gets the same view, big enough for wherever the elves got to
+    let bounds = Bounds::around(snapshots.iter().flat_map(|elves| elves.points())).unwrap();
+    snapshots
+        .iter()
+        .map(|elves| {
+            elves.within(bounds).frame(|(_, elf)| match elf {
+                Some(()) => [90, 200, 110],
+                None => [20, 20, 30],
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Day23;
+
+    common::examples!(Day23);
+    common::properties!(Day23, [1, 10, 70]);
+}
@@ -0,0 +1,432 @@
+//! Day 24: Blizzard Basin
+//!
+//! A valley of [Tile]s with blizzards blowing through it; the quickest way across, and then there,
+//! back and there again.
+
+#![warn(missing_docs)]
+
+use common::{
+    animate::{Playback, Steps},
+    error::ParseError,
+    generate,
+    nom::{grid, parse_complete},
+    point::{Dir4, Point2, Point3},
+    search::{astar, Found},
+    Part, Solution,
+};
+use nom::error::context;
+use rand::{rngs::StdRng, Rng};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Display,
+    rc::Rc,
+};
+
+/// Finds the quickest trips through the blizzards
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Input<'a> = Input;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(input: &Self::Input<'_>) -> Self::Part2 {
+        problem2(input)
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+
+    fn animate<'a>(input: &'a Self::Input<'_>, part: Part) -> Option<Box<dyn Playback + 'a>> {
+        let valley = Valley::new(input);
+        let route = Rc::new(valley.route(part));
+        let valley = Rc::new(valley);
+        Some(Box::new(Steps::new(
+            move || Journey {
+                valley: Rc::clone(&valley),
+                route: Rc::clone(&route),
+                minute: 0,
+            },
+            |journey| {
+                if journey.minute + 1 == journey.route.len() {
+                    return false;
+                }
+                journey.minute += 1;
+                true
+            },
+        )))
+    }
+}
+
+/// A tile of the valley as it starts out
+#[derive(Debug, PartialEq, Eq)]
+pub enum Tile {
+    /// `#`
+    Wall,
+    /// `.`
+    Empty,
+    /// A blizzard blowing the way the arrow points
+    Blizzard(Dir4),
+}
+
+/// A spot in the valley, with the minute it's at as `z`
+type Point = Point3<i64>;
+/// The valley a row at a time, walls and blizzards and all
+pub type Input = Vec<Vec<Tile>>;
+
+fn parse(input: &str) -> Result<Input, ParseError> {
+    parse_complete(
+        input,
+        context(
+            "valley",
+            grid(|c| match c {
+                '#' => Some(Tile::Wall),
+                '.' => Some(Tile::Empty),
+                c => Dir4::from_arrow(c).map(Tile::Blizzard),
+            }),
+        ),
+    )
+}
+
+/// A valley `size` tiles wide inside its walls and a quarter as high, with a blizzard on about a
+/// third of its tiles. Nothing blows up or down the columns with the entrance and exit in, since
+/// those blizzards would have nowhere to go. Blizzards can still shut the way back off for good,
+/// so a valley is only kept once there's a way there, back and there again.
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    loop {
+        let text = valley(rng, size);
+        let input = parse(&text).expect("generated valleys parse");
+        if Valley::new(&input).there_and_back().is_some() {
+            return text;
+        }
+    }
+}
+
+/// One try at a valley for [generate]
+fn valley(rng: &mut StdRng, size: usize) -> String {
+    let width = size.max(3);
+    let height = (width / 4).max(2);
+    generate::grid(width + 2, height + 2, |x, y| {
+        if (x, y) == (1, 0) || (x, y) == (width, height + 1) {
+            '.'
+        } else if x == 0 || y == 0 || x == width + 1 || y == height + 1 {
+            '#'
+        } else if rng.gen_ratio(1, 3) {
+            let across = x == 1 || x == width || rng.gen();
+            match (across, rng.gen::<bool>()) {
+                (true, true) => Dir4::Right,
+                (true, false) => Dir4::Left,
+                (false, true) => Dir4::Down,
+                (false, false) => Dir4::Up,
+            }
+            .arrow()
+        } else {
+            '.'
+        }
+    })
+}
+
+fn print(valley: &Input) -> String {
+    generate::lines(valley.iter().map(|row| {
+        row.iter()
+            .map(|tile| match tile {
+                Tile::Wall => '#',
+                Tile::Empty => '.',
+                Tile::Blizzard(dir) => dir.arrow(),
+            })
+            .collect::<String>()
+    }))
+}
+
+fn lcm(a: i64, b: i64) -> i64 {
+    fn gcd(x: i64, y: i64) -> i64 {
+        let mut x = x.abs();
+        let mut y = y.abs();
+
+        while y != 0 {
+            let t = y;
+            y = x % y;
+            x = t;
+        }
+
+        x
+    }
+    (a * b).abs() / gcd(a, b)
+}
+
+struct Valley {
+    /// Where every blizzard starts out, and which way it blows
+    blizzards: Vec<(Point2<i64>, Dir4)>,
+    points: BTreeSet<Point>,
+    height: i64,
+    width: i64,
+    cycle: i64,
+}
+
+impl Valley {
+    fn get_start(&self) -> Point2<i64> {
+        Point2::new(1, 0)
+    }
+
+    fn get_end(&self) -> Point2<i64> {
+        Point2::new(self.width - 2, self.height - 1)
+    }
+
+    fn is_free(&self, p: &Point) -> bool {
+        p.x >= 0 && p.y >= 0 && !self.points.contains(p)
+    }
+
+    fn is_wall(&self, p: Point2<i64>) -> bool {
+        let edge = p.x == 0 || p.y == 0 || p.x == self.width - 1 || p.y == self.height - 1;
+        edge && p != self.get_start() && p != self.get_end()
+    }
+
+    /// Where a blizzard that started at `p` is after `t` minutes, wrapping around inside the walls
+    fn blow(&self, p: Point2<i64>, dir: Dir4, t: i64) -> Point2<i64> {
+        let p = p - Point2::new(1, 1) + dir.delta() * t;
+        Point2::new(
+            p.x.rem_euclid(self.width - 2) + 1,
+            p.y.rem_euclid(self.height - 2) + 1,
+        )
+    }
+
+    fn new(input: &Input) -> Valley {
+        let height = input.len() as i64;
+        let width = input.first().unwrap().len() as i64;
+
+        // the blizzards are periodic, going round the inside of the walls, so we only need to
+        // generate up to a certain size
+        let cycle = lcm(height - 2, width - 2);
+
+        let mut valley = Valley {
+            blizzards: vec![],
+            points: BTreeSet::new(),
+            height,
+            width,
+            cycle,
+        };
+        for (y, row) in input.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if let Tile::Blizzard(dir) = cell {
+                    valley
+                        .blizzards
+                        .push((Point2::new(x as i64, y as i64), *dir));
+                }
+            }
+        }
+
+        // we're going to create a 3D map where the third dimension is time
+        let mut points: BTreeSet<Point> = BTreeSet::new();
+
+        for t in 0..cycle {
+            // the start and end are always available
+            points.insert(Point3::new(1, -1, t));
+            points.insert(Point3::new(width - 2, height, t));
+
+            for (y, row) in input.iter().enumerate() {
+                for (x, cell) in row.iter().enumerate() {
+                    let x = x as i64;
+                    let y = y as i64;
+                    match cell {
+                        // walls are always walls
+                        Tile::Wall => points.insert(Point3::new(x, y, t)),
+
+                        // insert the positions of where the blizzards will be at all points in
+                        // time, wrapping around inside the walls
+                        Tile::Blizzard(dir) => {
+                            let p = valley.blow(Point2::new(x, y), *dir, t);
+                            points.insert(Point3::new(p.x, p.y, t))
+                        }
+
+                        // we don't really need to do anything for the empty tiles
+                        Tile::Empty => false,
+                    };
+                }
+            }
+        }
+
+        valley.points = points;
+        valley
+    }
+
+    /// How long it takes to get across, back to the start and across again, if it can be done
+    fn there_and_back(&self) -> Option<i64> {
+        let (start, end) = (self.get_start(), self.get_end());
+        let t1 = self.travel_time(start, 0, end)?;
+        let t2 = self.travel_time(end, t1, start)?;
+        let t3 = self.travel_time(start, t1 + t2, end)?;
+        Some(t1 + t2 + t3)
+    }
+
+    fn travel_time(&self, start: Point2<i64>, time: i64, target: Point2<i64>) -> Option<i64> {
+        self.travel(start, time, target).map(|found| found.cost)
+    }
+
+    fn travel(
+        &self,
+        start: Point2<i64>,
+        time: i64,
+        target: Point2<i64>,
+    ) -> Option<Found<Point, i64>> {
+        let place = |p: &Point| Point2::new(p.x, p.y);
+
+        // get our neighbors in spacetime, every one of them is a minute away
+        let neighbors = |p: &Point| {
+            let next_time = (p.z + 1) % self.cycle;
+            let here = place(p);
+
+            // waiting where we are is a move too
+            std::iter::once(here)
+                .chain(Dir4::ALL.map(|dir| here + dir))
+                .map(move |next| Point3::new(next.x, next.y, next_time))
+                .filter(|next| self.is_free(next))
+                .map(|neighbor| (neighbor, 1))
+        };
+
+        // we can't possibly get there any faster than walking straight there
+        let heuristic = |p: &Point| place(p).manhattan(target);
+
+        astar(
+            [Point3::new(start.x, start.y, time % self.cycle)],
+            neighbors,
+            heuristic,
+            |p| place(p) == target,
+        )
+    }
+
+    /// Where the expedition is each minute: straight across for part 1, and there, back and there
+    /// again for part 2
+    fn route(&self, part: Part) -> Vec<Point2<i64>> {
+        let (start, end) = (self.get_start(), self.get_end());
+        let legs = match part {
+            Part::One => vec![(start, end)],
+            Part::Two => vec![(start, end), (end, start), (start, end)],
+        };
+
+        let mut route = vec![start];
+        for (from, to) in legs {
+            let time = route.len() as i64 - 1;
+            let path = self.travel(from, time, to).unwrap().path();
+            // each leg starts where the last one finished
+            route.extend(path.iter().skip(1).map(|p| Point2::new(p.x, p.y)));
+        }
+        route
+    }
+}
+
+/// The expedition partway along its route, with the blizzards blowing around it
+struct Journey {
+    valley: Rc<Valley>,
+    route: Rc<Vec<Point2<i64>>>,
+    minute: usize,
+}
+
+impl Display for Journey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let valley = &self.valley;
+        let mut blizzards: HashMap<Point2<i64>, Vec<Dir4>> = HashMap::new();
+        for &(p, dir) in &valley.blizzards {
+            let p = valley.blow(p, dir, self.minute as i64);
+            blizzards.entry(p).or_default().push(dir);
+        }
+
+        for y in 0..valley.height {
+            for x in 0..valley.width {
+                let p = Point2::new(x, y);
+                let c = if p == self.route[self.minute] {
+                    'E'
+                } else if valley.is_wall(p) {
+                    '#'
+                } else {
+                    match blizzards.get(&p).map(Vec::as_slice) {
+                        None => '.',
+                        Some([dir]) => dir.arrow(),
+                        Some(dirs) => char::from_digit(dirs.len() as u32, 10).unwrap_or('*'),
+                    }
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        writeln!(f, "minute {}", self.minute)
+    }
+}
+
+fn problem1(input: &Input) -> i64 {
+    let valley = Valley::new(input);
+    valley
+        .travel_time(valley.get_start(), 0, valley.get_end())
+        .unwrap()
+}
+
+fn problem2(input: &Input) -> i64 {
+    Valley::new(input).there_and_back().unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use common::test::get_raw_input;
+
+    use std::rc::Rc;
+
+    use common::Part;
+
+    use crate::{parse, Day24, Journey, Valley};
+
+    common::examples!(Day24);
+    common::properties!(Day24, [1, 3, 20, 120]);
+
+    #[test]
+    fn journey() {
+        let input = get_raw_input();
+        let input = parse(&input).unwrap();
+        let valley = Valley::new(&input);
+        let route = valley.route(Part::One);
+        assert_eq!(route.len(), 19);
+
+        let mut journey = Journey {
+            valley: Rc::new(valley),
+            route: Rc::new(route),
+            minute: 0,
+        };
+        assert_eq!(
+            journey.to_string(),
+            "#E######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#
+minute 0
+"
+        );
+
+        journey.minute = 1;
+        assert_eq!(
+            journey.to_string(),
+            "#.######
+#E>3.<.#
+#<..<<.#
+#>2.22.#
+#>v..^<#
+######.#
+minute 1
+"
+        );
+    }
+}
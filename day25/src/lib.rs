@@ -0,0 +1,260 @@
+//! Day 25: Full of Hot Air
+//!
+//! Sums fuel requirements written as [Snafu] numbers, balanced base five with digits from -2 to 2.
+
+#![warn(missing_docs)]
+
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use common::{
+    error::ParseError,
+    generate,
+    nom::{parse_complete, parse_lines, IResult},
+    Solution,
+};
+use nom::{branch::alt, character::complete::char, combinator::map, error::context, multi::many1};
+use rand::{rngs::StdRng, Rng};
+
+/// Adds up the SNAFU numbers on the fuel list
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+
+    type Input<'a> = Input;
+    type Part1 = Snafu;
+    type Part2 = &'static str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Snafu::parse_all(input)
+    }
+
+    fn part1(input: &Self::Input<'_>) -> Self::Part1 {
+        problem1(input)
+    }
+
+    fn part2(_input: &Self::Input<'_>) -> Self::Part2 {
+        "there is no part 2, go start the blender!"
+    }
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        generate(rng, size)
+    }
+
+    fn print(input: &Self::Input<'_>) -> String {
+        print(input)
+    }
+}
+
+/// The fuel requirements, one per line
+pub type Input = Vec<Snafu>;
+
+/// A single SNAFU digit
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub enum SnafuNumber {
+    /// `2`
+    Two,
+    /// `1`
+    One,
+    /// `0`
+    Zero,
+    /// `-`, minus one
+    Minus,
+    /// `=`, minus two
+    DoubleMinus,
+}
+
+impl From<&SnafuNumber> for i64 {
+    fn from(val: &SnafuNumber) -> Self {
+        match val {
+            SnafuNumber::Two => 2,
+            SnafuNumber::One => 1,
+            SnafuNumber::Zero => 0,
+            SnafuNumber::Minus => -1,
+            SnafuNumber::DoubleMinus => -2,
+        }
+    }
+}
+
+impl Debug for SnafuNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnafuNumber::Two => write!(f, "2"),
+            SnafuNumber::One => write!(f, "1"),
+            SnafuNumber::Zero => write!(f, "0"),
+            SnafuNumber::Minus => write!(f, "-"),
+            SnafuNumber::DoubleMinus => write!(f, "="),
+        }
+    }
+}
+
+impl Display for SnafuNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// A number written in SNAFU, its digits most significant first. It converts to and from `i64`,
+/// and parses from and displays as its digits.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Snafu {
+    numbers: Vec<SnafuNumber>,
+}
+
+impl Snafu {
+    fn parse(s: &str) -> IResult<&str, Snafu> {
+        context(
+            "snafu",
+            map(
+                many1(alt((
+                    map(char('2'), |_| SnafuNumber::Two),
+                    map(char('1'), |_| SnafuNumber::One),
+                    map(char('0'), |_| SnafuNumber::Zero),
+                    map(char('-'), |_| SnafuNumber::Minus),
+                    map(char('='), |_| SnafuNumber::DoubleMinus),
+                ))),
+                |numbers| Snafu { numbers },
+            ),
+        )(s)
+    }
+    fn parse_all(input: &str) -> Result<Input, ParseError> {
+        parse_lines(input, Snafu::parse)
+    }
+}
+
+impl From<&Snafu> for i64 {
+    fn from(value: &Snafu) -> Self {
+        let mut result = 0;
+        for (i, n) in value.numbers.iter().rev().enumerate() {
+            let power = i64::pow(5, i as u32);
+            let num: i64 = n.into();
+
+            result += num * power;
+        }
+
+        result
+    }
+}
+
+impl From<i64> for Snafu {
+    fn from(val: i64) -> Self {
+        let mut numbers: Vec<SnafuNumber> = vec![];
+        let mut n = val;
+        while n != 0 {
+            let remaining: i64 = n % 5;
+            let digit = match remaining {
+                2 => SnafuNumber::Two,
+                1 => SnafuNumber::One,
+                0 => SnafuNumber::Zero,
+                4 => SnafuNumber::Minus,
+                3 => SnafuNumber::DoubleMinus,
+                _ => unreachable!(),
+            };
+
+            numbers.push(digit);
+            n = (n + 2) / 5;
+        }
+
+        numbers.reverse();
+
+        Snafu { numbers }
+    }
+}
+
+impl FromStr for Snafu {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_complete(s, Snafu::parse)
+    }
+}
+
+impl Display for Snafu {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for num in self.numbers.iter() {
+            write!(f, "{num}")?;
+        }
+
+        write!(f, "")
+    }
+}
+
+/// `size` fuel requirements, in SNAFU, of up to twenty digits each
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    generate::lines((0..size.max(1)).map(|_| {
+        let digits = rng.gen_range(1..=20);
+        Snafu::from(rng.gen_range(1..=5_i64.pow(digits)))
+    }))
+}
+
+fn print(numbers: &Input) -> String {
+    generate::lines(numbers)
+}
+
+fn problem1(input: &Input) -> Snafu {
+    let n: i64 = input
+        .iter()
+        .map(|s| {
+            let i: i64 = s.into();
+            i
+        })
+        .sum();
+
+    n.into()
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use crate::{Day25, Snafu};
+
+    common::examples!(Day25);
+    common::properties!(Day25, [1, 10, 120]);
+
+    #[test]
+    fn examples() {
+        let data = [
+            (1, "1"),
+            (2, "2"),
+            (3, "1="),
+            (4, "1-"),
+            (5, "10"),
+            (6, "11"),
+            (7, "12"),
+            (8, "2="),
+            (9, "2-"),
+            (10, "20"),
+            (11, "21"),
+            (12, "22"),
+            (13, "1=="),
+            (14, "1=-"),
+            (15, "1=0"),
+            (15, "1=0"),
+            (20, "1-0"),
+            (2022, "1=11-2"),
+            (12345, "1-0---0"),
+            (314159265, "1121-1110-1=0"),
+        ];
+
+        for (dec, snafu) in data {
+            let result: Snafu = dec.into();
+            let expected: Snafu = snafu.parse().unwrap();
+
+            assert_eq!(result, expected);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn numbers_round_trip(n in 1..1_000_000_000_000_000_i64) {
+            let snafu = Snafu::from(n);
+            let parsed: Snafu = snafu.to_string().parse().unwrap();
+            prop_assert_eq!(i64::from(&parsed), n);
+            prop_assert_eq!(parsed, snafu);
+        }
+    }
+}
@@ -0,0 +1,9 @@
+#![no_main]
+
+use common::Solution;
+use day11::Day11;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day11::parse(input);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use common::Solution;
+use day12::Day12;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day12::parse(input);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use common::Solution;
+use day15::Day15;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day15::parse(input);
+});
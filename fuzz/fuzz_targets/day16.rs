@@ -0,0 +1,9 @@
+#![no_main]
+
+use common::Solution;
+use day16::Day16;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day16::parse(input);
+});
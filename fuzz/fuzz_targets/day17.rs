@@ -0,0 +1,9 @@
+#![no_main]
+
+use common::Solution;
+use day17::Day17;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day17::parse(input);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use common::Solution;
+use day19::Day19;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day19::parse(input);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use common::Solution;
+use day20::Day20;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day20::parse(input);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use common::Solution;
+use day21::Day21;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day21::parse(input);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use common::Solution;
+use day22::Day22;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day22::parse(input);
+});